@@ -0,0 +1,237 @@
+use crate::error::{AocError, Result};
+use crate::input::InputSource;
+use crate::{days, runner, Part};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The answers files shipped with the repository, one per year.
+pub const ANSWERS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers");
+
+/// The answers file of `year`, as `<year>.txt` in [`ANSWERS_DIR`].
+pub fn answers_file(year: u16) -> PathBuf {
+    Path::new(ANSWERS_DIR).join(format!("{}.txt", year))
+}
+
+/// Placeholder for an answer that is not known yet.
+pub const PENDING: &str = "?";
+
+/// (day, part, input name)
+pub type Key = (u8, u8, String);
+
+/// Whether `name` can be stored as an input name, the fields of an entry
+/// are separated by whitespace.
+pub fn is_valid_input_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains(char::is_whitespace)
+}
+
+/// Known-good answers, keyed by day, part and input name.
+///
+/// Stored as a text file with one `<day> <part> <input> <answer>` entry per
+/// line, `#` starts a comment. An answer of [`PENDING`] marks a part that
+/// still has to be solved.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    entries: BTreeMap<Key, String>,
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> std::result::Result<Self, String> {
+        let mut entries = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.splitn(4, char::is_whitespace).collect();
+            let entry = match fields.as_slice() {
+                [day, part, input, answer] => day
+                    .parse::<u8>()
+                    .ok()
+                    .zip(part.parse::<u8>().ok().filter(|p| *p == 1 || *p == 2))
+                    .map(|(day, part)| ((day, part, input.to_string()), answer.trim())),
+                _ => None,
+            };
+            match entry {
+                Some((key, answer)) => {
+                    entries.insert(key, answer.to_string());
+                }
+                None => {
+                    return Err(format!(
+                        "line {}: expected '<day> <part> <input> <answer>'",
+                        i + 1
+                    ))
+                }
+            }
+        }
+        Ok(Self { entries })
+    }
+
+    /// Loads the answers at `path`, a missing file counts as empty.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => {
+                Self::parse(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn get(&self, day: u8, part: Part, input: &str) -> Option<&str> {
+        self.entries
+            .get(&(day, part.number(), input.to_string()))
+            .map(String::as_str)
+    }
+
+    /// Records an answer, returns the previous one if it was replaced.
+    pub fn set(&mut self, day: u8, part: Part, input: &str, answer: &str) -> Option<String> {
+        self.entries
+            .insert((day, part.number(), input.to_string()), answer.to_string())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Key, &String)> {
+        self.entries.iter()
+    }
+}
+
+impl std::fmt::Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "# day part input answer")?;
+        for ((day, part, input), answer) in self.entries.iter() {
+            writeln!(f, "{} {} {} {}", day, part, input, answer)?;
+        }
+        Ok(())
+    }
+}
+
+/// Result of checking one recorded answer.
+pub struct Check {
+    pub day: u8,
+    pub part: Part,
+    pub input: String,
+    pub expected: String,
+    pub actual: Result<String>,
+}
+
+impl Check {
+    pub fn passed(&self) -> bool {
+        self.actual.as_deref() == Ok(self.expected.as_str())
+    }
+
+    /// No answer has been recorded yet, so there is nothing to compare.
+    pub fn is_pending(&self) -> bool {
+        self.expected == PENDING
+    }
+}
+
+/// Re-runs the solvers of `year` for every recorded answer of the selected
+/// days, or of all days if `days` is empty.
+pub fn verify(answers: &Answers, year: u16, only_days: &[u8]) -> Vec<Check> {
+    // Group by day and input, so each input is only parsed once
+    let mut grouped: BTreeMap<(u8, &str), Vec<(Part, &str)>> = BTreeMap::new();
+    for ((day, part, input), expected) in answers.iter() {
+        if only_days.is_empty() || only_days.contains(day) {
+            let part = Part::from_number(*part).unwrap();
+            grouped
+                .entry((*day, input.as_str()))
+                .or_default()
+                .push((part, expected.as_str()));
+        }
+    }
+
+    let mut checks = Vec::new();
+    for ((day, input), expected) in grouped {
+        let parts: Vec<Part> = expected.iter().map(|(part, _)| *part).collect();
+        let actual: Vec<Result<String>> = match days::get(year, day) {
+            None => vec![Err(AocError::new(day, "day is not implemented")); parts.len()],
+            Some(solver) => match InputSource::named(year, day, input).load(solver.as_ref()) {
+                Ok(text) => runner::run(solver.as_ref(), &text, &parts)
+                    .into_iter()
+                    .map(|r| r.answer)
+                    .collect(),
+                Err(e) => {
+                    let msg = format!("could not read input '{}': {}", input, e);
+                    vec![Err(AocError::new(day, msg)); parts.len()]
+                }
+            },
+        };
+
+        for ((part, expected), actual) in expected.into_iter().zip(actual) {
+            checks.push(Check {
+                day,
+                part,
+                input: input.to_string(),
+                expected: expected.to_string(),
+                actual,
+            });
+        }
+    }
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_format() {
+        let text = "# comment\n7 2 input 7872\n\n1 1 alice 514579\n";
+        let answers = Answers::parse(text).unwrap();
+        assert_eq!(Some("7872"), answers.get(7, Part::Two, "input"));
+        assert_eq!(Some("514579"), answers.get(1, Part::One, "alice"));
+        assert_eq!(None, answers.get(1, Part::Two, "alice"));
+
+        let formatted = answers.to_string();
+        assert_eq!(answers, Answers::parse(&formatted).unwrap());
+        assert!(Answers::parse("7 3 input 1").is_err());
+        assert!(Answers::parse("7 2 input").is_err());
+    }
+
+    #[test]
+    fn test_input_names() {
+        let mut answers = Answers::default();
+        for name in ["alice", "bob-2", "input"].iter() {
+            assert!(is_valid_input_name(name));
+            answers.set(1, Part::One, name, "514579 with spaces");
+        }
+        assert_eq!(answers, Answers::parse(&answers.to_string()).unwrap());
+
+        // Would come back as the name `my` and the answer `input 514579`
+        assert!(!is_valid_input_name("my input"));
+        assert!(!is_valid_input_name(""));
+    }
+
+    #[test]
+    fn test_set() {
+        let mut answers = Answers::default();
+        assert_eq!(None, answers.set(9, Part::One, "input", "1"));
+        assert_eq!(
+            Some("1".to_string()),
+            answers.set(9, Part::One, "input", "2")
+        );
+        assert_eq!(Some("2"), answers.get(9, Part::One, "input"));
+    }
+
+    #[test]
+    fn test_recorded_answers() {
+        let answers = Answers::parse(include_str!("../answers/2020.txt")).unwrap();
+        for check in verify(&answers, 2020, &[]) {
+            assert!(
+                check.passed() || check.is_pending(),
+                "day {} part {} on '{}': expected {}, got {:?}",
+                check.day,
+                check.part,
+                check.input,
+                check.expected,
+                check.actual
+            );
+        }
+    }
+}
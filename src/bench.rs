@@ -0,0 +1,149 @@
+use crate::error::Result;
+use crate::json::Json;
+use crate::{Part, Solver};
+use std::time::{Duration, Instant};
+
+/// Summary of the samples taken for one stage of a solver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// Nearest-rank median and 95th percentile, `None` without samples.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_unstable();
+        let rank = |p: f64| {
+            let rank = (p * samples.len() as f64).ceil() as usize;
+            samples[rank.clamp(1, samples.len()) - 1]
+        };
+        Some(Stats {
+            median: rank(0.5),
+            p95: rank(0.95),
+        })
+    }
+
+    pub fn to_json(self) -> Json {
+        Json::object(vec![
+            ("median_ns", Json::from(self.median.as_nanos())),
+            ("p95_ns", Json::from(self.p95.as_nanos())),
+        ])
+    }
+}
+
+/// Timings of one day over a number of iterations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayBench {
+    pub day: u8,
+    pub iterations: usize,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+impl DayBench {
+    pub fn stages(&self) -> [(&'static str, Stats); 3] {
+        [
+            ("parse", self.parse),
+            ("part 1", self.part1),
+            ("part 2", self.part2),
+        ]
+    }
+
+    pub fn to_json(self) -> Json {
+        Json::object(vec![
+            ("day", Json::from(self.day)),
+            ("iterations", Json::from(self.iterations)),
+            ("parse", self.parse.to_json()),
+            ("part1", self.part1.to_json()),
+            ("part2", self.part2.to_json()),
+        ])
+    }
+}
+
+/// Times parsing and both parts separately, `iterations` times each.
+///
+/// The first error aborts the benchmark, timing a failing solver is
+/// meaningless.
+pub fn bench(solver: &dyn Solver, input: &str, iterations: usize) -> Result<DayBench> {
+    let iterations = iterations.max(1);
+    let mut parse = Vec::with_capacity(iterations);
+    let mut part1 = Vec::with_capacity(iterations);
+    let mut part2 = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let parsed = solver.parse(input)?;
+        parse.push(start.elapsed());
+
+        let start = Instant::now();
+        solver.solve(parsed.as_ref(), Part::One)?;
+        part1.push(start.elapsed());
+
+        let start = Instant::now();
+        solver.solve(parsed.as_ref(), Part::Two)?;
+        part2.push(start.elapsed());
+    }
+
+    // There is at least one sample of each
+    Ok(DayBench {
+        day: solver.day(),
+        iterations,
+        parse: Stats::from_samples(parse).unwrap(),
+        part1: Stats::from_samples(part1).unwrap(),
+        part2: Stats::from_samples(part2).unwrap(),
+    })
+}
+
+/// Formats a duration with a unit suited to its size, e.g. `12.3 ms`.
+pub fn format_duration(d: Duration) -> String {
+    let ns = d.as_nanos() as f64;
+    if ns < 1e3 {
+        format!("{} ns", ns)
+    } else if ns < 1e6 {
+        format!("{:.1} µs", ns / 1e3)
+    } else if ns < 1e9 {
+        format!("{:.1} ms", ns / 1e6)
+    } else {
+        format!("{:.2} s", ns / 1e9)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn test_stats() {
+        let samples: Vec<Duration> = (1..=20).rev().map(Duration::from_millis).collect();
+        let stats = Stats::from_samples(samples).unwrap();
+        assert_eq!(Duration::from_millis(10), stats.median);
+        assert_eq!(Duration::from_millis(19), stats.p95);
+
+        let single = Stats::from_samples(vec![Duration::from_millis(3)]).unwrap();
+        assert_eq!(single.median, single.p95);
+        assert_eq!(None, Stats::from_samples(vec![]));
+    }
+
+    #[test]
+    fn test_bench() {
+        let day06 = days::get(2020, 6).unwrap();
+        let res = bench(day06.as_ref(), "abc\n\nab\nb", 3).unwrap();
+        assert_eq!(6, res.day);
+        assert_eq!(3, res.iterations);
+        assert!(bench(day06.as_ref(), "aBc", 3).is_err());
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!("999 ns", format_duration(Duration::from_nanos(999)));
+        assert_eq!("1.5 µs", format_duration(Duration::from_nanos(1500)));
+        assert_eq!("12.3 ms", format_duration(Duration::from_micros(12300)));
+        assert_eq!("2.00 s", format_duration(Duration::from_secs(2)));
+    }
+}
@@ -0,0 +1,44 @@
+use aoc2020::server;
+use std::env;
+use std::net::TcpListener;
+use std::process;
+
+const USAGE: &str = "Usage:
+    aoc-server [--addr <host:port>]";
+
+/// Listens on localhost unless told otherwise, the solvers are not meant to
+/// be exposed to the network.
+const DEFAULT_ADDR: &str = "127.0.0.1:8020";
+
+fn main() {
+    let mut addr = DEFAULT_ADDR.to_string();
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), args.next()) {
+            ("--addr", Some(value)) => addr = value,
+            _ => {
+                eprintln!("Unexpected argument '{}'\n\n{}", arg, USAGE);
+                process::exit(2);
+            }
+        }
+    }
+
+    let listener = match TcpListener::bind(&addr) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("Could not listen on {}: {}", addr, e);
+            process::exit(1);
+        }
+    };
+    println!("Listening on http://{}", addr);
+    println!(
+        "Try: curl --data-binary @src/days/y2020/day07-input.txt http://{}/2020/day/7/part/2",
+        addr
+    );
+
+    if let Err(e) = server::serve(listener) {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,1037 @@
+use aoc2020::answers::{self, Answers, Check};
+use aoc2020::bench::{self, format_duration};
+use aoc2020::days::{self, Year};
+use aoc2020::examples;
+use aoc2020::fetch::{self, Fetcher, Origin};
+use aoc2020::http;
+use aoc2020::input::{self, InputSource};
+use aoc2020::json::Json;
+use aoc2020::memory::{self, format_bytes};
+use aoc2020::runner::{self, PartResult, RunAll};
+use aoc2020::scaffold;
+use aoc2020::submit::{self, History, Submission, Verdict};
+use aoc2020::trace::{self, Level};
+use aoc2020::tui;
+use aoc2020::watch::{self, Watcher};
+use aoc2020::{Part, Solver};
+use std::env;
+use std::path::Path;
+use std::process::{self, Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const USAGE: &str = "Usage:
+    aoc run <day|all> [--part <1|2>] [--format <table|json|answers>] [--input <path|->]
+            [--inputs <dir>] [--budget <ms>] [--jobs <n>] [-v|-vv]
+    aoc verify [day|all] [--answers <path>]
+    aoc examples [day|all]
+    aoc record <day> <part> [answer] [--input <path>] [--answers <path>]
+    aoc bench <day|all> [--iterations <n>] [--format <table|json>] [--input <path|->]
+    aoc new <day> [--answers <path>]
+    aoc fetch <day> [--session <token>] [--base-url <url>]
+    aoc submit <day> <part> [--session <token>] [--base-url <url>] [--answers <path>]
+    aoc watch <day> [--interval <ms>]
+    aoc tui [--answers <path>]
+
+Every command takes --year <year>, by default AOC_YEAR or the latest year.
+The dashboard of `tui` needs a Unix terminal.";
+
+/// How often `watch` looks for changed files.
+const DEFAULT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Where `watch` builds, apart from the binary running it.
+const WATCH_TARGET_DIR: &str = "target/watch";
+
+/// How long `run` waits for a solver it gave up on before timing the next
+/// input.
+const ABANDONED_WAIT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Table,
+    Json,
+    /// The format of the answers file, see [`Answers`].
+    Answers,
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let res = take_year(args).and_then(|(year, args)| match args.first().map(String::as_str) {
+        Some("run") => cmd_run(year, &args[1..]),
+        Some("verify") => cmd_verify(year, &args[1..]),
+        Some("examples") => cmd_examples(year, &args[1..]),
+        Some("record") => cmd_record(year, &args[1..]),
+        Some("bench") => cmd_bench(year, &args[1..]),
+        Some("new") => cmd_new(year, &args[1..]),
+        Some("watch") => cmd_watch(year, &args[1..]),
+        Some("fetch") => cmd_fetch(year, &args[1..]),
+        Some("submit") => cmd_submit(year, &args[1..]),
+        Some("tui") => cmd_tui(year, &args[1..]),
+        Some(cmd) => Err(format!("Unknown command '{}'", cmd)),
+        None => Err("Missing command".to_string()),
+    });
+
+    match res {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(msg) => {
+            eprintln!("{}\n\n{}", msg, USAGE);
+            process::exit(2);
+        }
+    }
+}
+
+/// Runs the selected days, returns false if any part failed.
+///
+/// Days and parts run concurrently on `--jobs` threads. With `--inputs` every
+/// input in a directory is run through a single day instead, each within a
+/// time budget. With `-v` the solvers write intermediate results to stderr,
+/// with `-vv` every step as well.
+fn cmd_run(year: u16, args: &[String]) -> Result<bool, String> {
+    let year = registered(year)?;
+    let mut which: Option<&str> = None;
+    let mut parts: Vec<Part> = Part::BOTH.to_vec();
+    let mut format = Format::Table;
+    let mut source = InputSource::Embedded;
+    let mut inputs_dir: Option<&str> = None;
+    let mut budget: Option<Duration> = None;
+    let mut jobs = thread::available_parallelism().map_or(1, |n| n.get());
+    let mut verbosity = 0;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" | "-p" => {
+                let part = args.next().ok_or("Missing value for --part")?;
+                parts = vec![parse_part(part)?];
+            }
+            "--format" => format = parse_format(args.next())?,
+            "--input" | "-i" => {
+                let path = args.next().ok_or("Missing value for --input")?;
+                source = InputSource::from_arg(path);
+            }
+            "--jobs" | "-j" => {
+                let n = args.next().ok_or("Missing value for --jobs")?;
+                jobs = n
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| format!("Invalid job count '{}'", n))?;
+            }
+            "--inputs" => inputs_dir = Some(args.next().ok_or("Missing value for --inputs")?),
+            "--budget" => {
+                let ms = args.next().ok_or("Missing value for --budget")?;
+                let ms = ms
+                    .parse::<u64>()
+                    .map_err(|_| format!("Invalid budget '{}'", ms))?;
+                budget = Some(Duration::from_millis(ms));
+            }
+            "-v" | "--verbose" => verbosity += 1,
+            "-vv" => verbosity += 2,
+            s if which.is_none() => which = Some(s),
+            s => return Err(format!("Unexpected argument '{}'", s)),
+        }
+    }
+
+    trace::set_level(Level::from_verbosity(verbosity));
+
+    let solvers = select_days(year, which.ok_or("Missing day")?)?;
+    if solvers.len() > 1 && (source != InputSource::Embedded || inputs_dir.is_some()) {
+        return Err("--input and --inputs can only be used with a single day".to_string());
+    }
+    if source != InputSource::Embedded && inputs_dir.is_some() {
+        return Err("--input and --inputs cannot be combined".to_string());
+    }
+
+    let sources = match inputs_dir {
+        Some(dir) => {
+            let sources =
+                input::inputs_in(dir).map_err(|e| format!("Could not read {}: {}", dir, e))?;
+            if sources.is_empty() {
+                return Err(format!("No .txt inputs in {}", dir));
+            }
+            sources
+        }
+        None => vec![source],
+    };
+
+    let mut loaded: Vec<(String, Box<dyn Solver>, String)> = Vec::new();
+    for solver in solvers {
+        for source in sources.iter() {
+            match source.load(solver.as_ref()) {
+                Ok(input) => loaded.push((source.name(), year.get(solver.day()).unwrap(), input)),
+                Err(e) => {
+                    eprintln!("Could not read {}: {}", source, e);
+                    return Ok(false);
+                }
+            }
+        }
+    }
+
+    let mut results: Vec<(String, PartResult)> = Vec::new();
+    let mut timings: Option<RunAll> = None;
+    // One input of many must not hold up the others
+    if budget.is_some() || inputs_dir.is_some() {
+        // A solver given up on still running competes with the next ones,
+        // their timings are marked if it does not stop soon enough
+        let mut still_running: Option<String> = None;
+        for (name, solver, input) in loaded {
+            let day = solver.day();
+            let mut res = runner::run_with_budget(solver, input, &parts, budget);
+            if let Some(other) = &still_running {
+                for r in res.parts.iter_mut() {
+                    r.details
+                        .push(("timed_alongside", Json::from(other.as_str())));
+                }
+            }
+            if !res.wait(ABANDONED_WAIT) {
+                eprintln!(
+                    "Day {} on {} is still running, the timings after it may be off",
+                    day, name
+                );
+                still_running = Some(name.clone());
+            }
+            results.extend(res.parts.into_iter().map(|r| (name.clone(), r)));
+        }
+    } else {
+        let names: Vec<String> = loaded.iter().map(|(name, _, _)| name.clone()).collect();
+        let days: Vec<(Box<dyn Solver>, String)> = loaded
+            .into_iter()
+            .map(|(_, solver, input)| (solver, input))
+            .collect();
+        let mut run = runner::run_all(&days, &parts, jobs);
+        for (name, day) in names.iter().zip(run.days.iter_mut()) {
+            results.extend(day.parts.drain(..).map(|r| (name.clone(), r)));
+        }
+        timings = Some(run);
+    }
+
+    if format == Format::Json {
+        let results: Vec<Json> = results
+            .iter()
+            .map(|(name, r)| match r.to_json() {
+                Json::Object(mut fields) if inputs_dir.is_some() => {
+                    fields.insert(2, ("input".to_string(), Json::from(name.as_str())));
+                    Json::Object(fields)
+                }
+                json => json,
+            })
+            .collect();
+        println!("{}", Json::from(results));
+    } else if format == Format::Answers {
+        // Failed parts have no answer to record
+        let mut answers = Answers::default();
+        for (name, r) in results.iter() {
+            match &r.answer {
+                Ok(answer) => {
+                    answers.set(r.day, r.part, name, answer);
+                }
+                Err(e) => eprintln!("Day {} part {}: {}: {}", r.day, r.part, r.status, e),
+            }
+            if let Some(over) = r.over_budget() {
+                eprintln!(
+                    "Day {} part {}: {}",
+                    r.day,
+                    r.part,
+                    describe_over_budget(r, over)
+                );
+            }
+        }
+        print!("{}", answers);
+    } else {
+        let rows = results
+            .iter()
+            .map(|(name, r)| {
+                let answer = match (&r.answer, r.over_budget()) {
+                    (Ok(answer), Some(over)) => {
+                        format!("{} ({})", answer, describe_over_budget(r, over))
+                    }
+                    (Ok(answer), None) => answer.clone(),
+                    (Err(e), _) => format!("{}: {}", r.status, e),
+                };
+                let mut row = vec![r.day.to_string(), r.part.to_string()];
+                if inputs_dir.is_some() {
+                    row.push(name.clone());
+                }
+                row.extend(vec![answer, format_duration(r.elapsed)]);
+                if memory::ENABLED {
+                    let memory = r.memory.unwrap_or_default();
+                    row.extend(vec![
+                        memory.allocations.to_string(),
+                        format_bytes(memory.bytes),
+                        format_bytes(memory.peak),
+                    ]);
+                }
+                row
+            })
+            .collect();
+        let mut header = vec!["Day", "Part"];
+        if inputs_dir.is_some() {
+            header.push("Input");
+        }
+        header.extend(&["Answer", "Time"]);
+        if memory::ENABLED {
+            header.extend(&["Allocs", "Bytes", "Peak"]);
+        }
+        print_table(&header, rows);
+
+        if let Some(run) = timings {
+            if run.days.len() > 1 {
+                println!();
+                let rows = run
+                    .days
+                    .iter()
+                    .map(|d| vec![d.day.to_string(), format_duration(d.elapsed)])
+                    .collect();
+                print_table(&["Day", "Time"], rows);
+            }
+            println!(
+                "\n{} wall clock on {} thread{}",
+                format_duration(run.elapsed),
+                jobs,
+                if jobs == 1 { "" } else { "s" }
+            );
+        }
+    }
+
+    Ok(results.iter().all(|(_, r)| r.is_ok()))
+}
+
+/// Checks the recorded answers, returns false on any mismatch.
+fn cmd_verify(year: u16, args: &[String]) -> Result<bool, String> {
+    let mut which: Option<&str> = None;
+    let mut path = answers::answers_file(year).display().to_string();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" => path = args.next().ok_or("Missing value for --answers")?.clone(),
+            s if which.is_none() => which = Some(s),
+            s => return Err(format!("Unexpected argument '{}'", s)),
+        }
+    }
+
+    let only_days: Vec<u8> = match which {
+        None | Some("all") => vec![],
+        Some(day) => vec![parse_day(day)?],
+    };
+
+    let answers = load_answers(&path)?;
+    let checks = answers::verify(&answers, year, &only_days);
+
+    let rows = check_rows(&checks);
+    print_table(
+        &["Day", "Part", "Input", "Expected", "Actual", "Status"],
+        rows,
+    );
+
+    let pending = checks.iter().filter(|c| c.is_pending()).count();
+    let failed = checks
+        .iter()
+        .filter(|c| !c.passed() && !c.is_pending())
+        .count();
+    println!(
+        "\n{} of {} answers verified, {} pending",
+        checks.len() - pending - failed,
+        checks.len() - pending,
+        pending
+    );
+
+    Ok(failed == 0)
+}
+
+/// Checks every example against its sidecar answers, returns false on any
+/// mismatch.
+fn cmd_examples(year: u16, args: &[String]) -> Result<bool, String> {
+    let year = registered(year)?;
+    let only_day = match args {
+        [] => None,
+        [which] if which == "all" => None,
+        [day] => Some(parse_day(day)?),
+        _ => return Err(format!("Unexpected argument '{}'", args[1])),
+    };
+
+    let found =
+        examples::discover(year.dir).map_err(|e| format!("Could not read {}: {}", year.dir, e))?;
+
+    let mut checks: Vec<Check> = Vec::new();
+    for example in found.iter() {
+        if only_day.is_some_and(|day| day != example.day) {
+            continue;
+        }
+        if example.expected.is_empty() {
+            let sidecar = Path::new(year.dir)
+                .join(&example.name)
+                .with_extension("answers");
+            eprintln!(
+                "No expected answers for {}, add them to {}",
+                example.name,
+                sidecar.display()
+            );
+        }
+        checks.extend(examples::check(year.year, example));
+    }
+
+    let rows = check_rows(&checks);
+    print_table(
+        &["Day", "Part", "Example", "Expected", "Actual", "Status"],
+        rows,
+    );
+
+    let passed = checks.iter().filter(|c| c.passed()).count();
+    println!("\n{} of {} example answers passed", passed, checks.len());
+
+    Ok(passed == checks.len())
+}
+
+/// Stores an answer in the answers file, computing it if not given.
+///
+/// An input from elsewhere is copied into the inputs directory, so that
+/// `verify` finds it again by its name.
+fn cmd_record(year: u16, args: &[String]) -> Result<bool, String> {
+    let mut positional: Vec<&str> = Vec::new();
+    let mut source = InputSource::Embedded;
+    let mut path = answers::answers_file(year).display().to_string();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" | "-i" => {
+                let path = args.next().ok_or("Missing value for --input")?;
+                source = InputSource::from_arg(path);
+            }
+            "--answers" => path = args.next().ok_or("Missing value for --answers")?.clone(),
+            s => positional.push(s),
+        }
+    }
+
+    let (day, part, answer) = match positional.as_slice() {
+        [day, part] => (parse_day(day)?, parse_part(part)?, None),
+        [day, part, answer] => (parse_day(day)?, parse_part(part)?, Some(answer.to_string())),
+        _ => return Err("Expected <day> <part> [answer]".to_string()),
+    };
+    // Verifying reads the input again by its name
+    if source == InputSource::Stdin {
+        return Err("Cannot record an answer for stdin, pass the input as a file".to_string());
+    }
+
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let solver = days::get(year, day)
+                .ok_or_else(|| format!("Day {} of {} is not implemented", day, year))?;
+            let input = source
+                .load(solver.as_ref())
+                .map_err(|e| format!("Could not read {}: {}", source, e))?;
+            let res = runner::run(solver.as_ref(), &input, &[part]).remove(0);
+            match res.answer {
+                Ok(answer) => answer,
+                Err(e) => {
+                    eprintln!("Nothing recorded, {}", e);
+                    return Ok(false);
+                }
+            }
+        }
+    };
+
+    let mut answers = load_answers(&path)?;
+    let stored = source
+        .store(year, day)
+        .map_err(|e| format!("Could not store {}: {}", source, e))?;
+    if stored != source {
+        println!("Stored {} as {}", source, stored);
+    }
+    let name = stored.name();
+    match answers.set(day, part, &name, &answer) {
+        Some(old) if old != answer => println!(
+            "Day {} part {} ({}): {} (was {})",
+            day, part, name, answer, old
+        ),
+        _ => println!("Day {} part {} ({}): {}", day, part, name, answer),
+    }
+    answers
+        .save(&path)
+        .map_err(|e| format!("Could not write {}: {}", path, e))?;
+
+    Ok(true)
+}
+
+/// Times the selected days, returns false if any of them failed.
+fn cmd_bench(year: u16, args: &[String]) -> Result<bool, String> {
+    let year = registered(year)?;
+    let mut which: Option<&str> = None;
+    let mut iterations = 10;
+    let mut format = Format::Table;
+    let mut source = InputSource::Embedded;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--iterations" | "-n" => {
+                let n = args.next().ok_or("Missing value for --iterations")?;
+                iterations = n
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| format!("Invalid iteration count '{}'", n))?;
+            }
+            "--format" => format = parse_format(args.next())?,
+            "--input" | "-i" => {
+                let path = args.next().ok_or("Missing value for --input")?;
+                source = InputSource::from_arg(path);
+            }
+            s if which.is_none() => which = Some(s),
+            s => return Err(format!("Unexpected argument '{}'", s)),
+        }
+    }
+
+    let solvers = select_days(year, which.ok_or("Missing day")?)?;
+    if solvers.len() > 1 && source != InputSource::Embedded {
+        return Err("--input can only be used with a single day".to_string());
+    }
+    if format == Format::Answers {
+        return Err("Benchmarks have no answers format".to_string());
+    }
+
+    let mut ok = true;
+    let mut results = Vec::new();
+    for solver in solvers.iter() {
+        let input = match source.load(solver.as_ref()) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Could not read {}: {}", source, e);
+                return Ok(false);
+            }
+        };
+        match bench::bench(solver.as_ref(), &input, iterations) {
+            Ok(res) => results.push(res),
+            Err(e) => {
+                eprintln!("{}", e);
+                ok = false;
+            }
+        }
+    }
+
+    if format == Format::Json {
+        let results: Vec<Json> = results.into_iter().map(|r| r.to_json()).collect();
+        println!("{}", Json::from(results));
+    } else {
+        let rows = results
+            .iter()
+            .flat_map(|r| {
+                r.stages()
+                    .iter()
+                    .map(move |(stage, stats)| {
+                        vec![
+                            r.day.to_string(),
+                            stage.to_string(),
+                            format_duration(stats.median),
+                            format_duration(stats.p95),
+                        ]
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        println!("{} iterations", iterations);
+        print_table(&["Day", "Stage", "Median", "p95"], rows);
+    }
+
+    Ok(ok)
+}
+
+/// Scaffolds a new day and registers it, refusing to overwrite anything.
+/// The year is scaffolded too if it has no days yet.
+fn cmd_new(year: u16, args: &[String]) -> Result<bool, String> {
+    let mut day: Option<u8> = None;
+    let mut path = answers::answers_file(year).display().to_string();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" => path = args.next().ok_or("Missing value for --answers")?.clone(),
+            s if day.is_none() => day = Some(parse_day(s)?),
+            s => return Err(format!("Unexpected argument '{}'", s)),
+        }
+    }
+    let day = day.ok_or("Missing day")?;
+    if !(1..=25).contains(&day) {
+        return Err(format!("Invalid day '{}', should be between 1 and 25", day));
+    }
+
+    let mut answers = load_answers(&path)?;
+    if days::year(year).is_none() {
+        match scaffold::create_year(days::DAYS_DIR, year) {
+            Ok(registry) => println!("Created {}", registry.display()),
+            Err(e) => {
+                eprintln!("Nothing created, {}", e);
+                return Ok(false);
+            }
+        }
+    }
+    let dir = Path::new(days::DAYS_DIR).join(format!("y{}", year));
+    match scaffold::create_day(&dir, day) {
+        Ok(files) => {
+            for file in files.iter() {
+                println!("Created {}", file.display());
+            }
+        }
+        Err(e) => {
+            eprintln!("Nothing created, {}", e);
+            return Ok(false);
+        }
+    }
+
+    for part in Part::BOTH.iter() {
+        if answers.get(day, *part, "input").is_none() {
+            answers.set(day, *part, "input", answers::PENDING);
+        }
+    }
+    answers
+        .save(&path)
+        .map_err(|e| format!("Could not write {}: {}", path, e))?;
+
+    println!(
+        "Find todays instructions at: https://adventofcode.com/{}/day/{}",
+        year, day
+    );
+    println!("Fetch todays input with: aoc fetch {} --year {}", day, year);
+
+    Ok(true)
+}
+
+/// Downloads the input of a day into the cache and its `dayNN-input.txt`,
+/// leaving an input that is already there alone.
+///
+/// The session token is the `session` cookie of a logged in browser, taken
+/// from `--session` or `AOC_SESSION`. The website can be swapped for another
+/// with `--base-url` or `AOC_BASE_URL`.
+fn cmd_fetch(year: u16, args: &[String]) -> Result<bool, String> {
+    let year = registered(year)?;
+    let mut day: Option<u8> = None;
+    let mut session = env::var("AOC_SESSION").ok();
+    let mut base_url =
+        env::var("AOC_BASE_URL").unwrap_or_else(|_| fetch::DEFAULT_BASE_URL.to_string());
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--session" => {
+                session = Some(args.next().ok_or("Missing value for --session")?.clone())
+            }
+            "--base-url" => base_url = args.next().ok_or("Missing value for --base-url")?.clone(),
+            s if day.is_none() => day = Some(parse_day(s)?),
+            s => return Err(format!("Unexpected argument '{}'", s)),
+        }
+    }
+    let day = day.ok_or("Missing day")?;
+    if !(1..=25).contains(&day) {
+        return Err(format!("Invalid day '{}', should be between 1 and 25", day));
+    }
+    let fetcher = Fetcher {
+        client: &http::System,
+        year: year.year,
+        base_url,
+        session: require_session(session)?,
+        cache_dir: fetch::cache_dir(year.year),
+    };
+    let target = Path::new(year.dir).join(format!("day{:02}-input.txt", day));
+    match fetcher.install(day, &target) {
+        Ok(Some(origin)) => {
+            if origin == Origin::Downloaded {
+                println!(
+                    "Downloaded the input of day {} into {}",
+                    day,
+                    fetch::CACHE_DIR
+                );
+            }
+            println!("Wrote {}, rebuild to embed it", target.display());
+            Ok(true)
+        }
+        Ok(None) => {
+            println!("Left {} as it is, it is not empty", target.display());
+            Ok(true)
+        }
+        Err(e) => {
+            eprintln!("Could not fetch the input of day {}: {}", day, e);
+            Ok(false)
+        }
+    }
+}
+
+/// Solves a part on the puzzle input and submits the answer, unless the
+/// history of submissions shows it is wrong or it is too early to try again.
+/// A right answer is also recorded in the answers file.
+///
+/// The session token and website are given as for `fetch`.
+fn cmd_submit(year: u16, args: &[String]) -> Result<bool, String> {
+    let mut positional: Vec<&str> = Vec::new();
+    let mut session = env::var("AOC_SESSION").ok();
+    let mut base_url =
+        env::var("AOC_BASE_URL").unwrap_or_else(|_| fetch::DEFAULT_BASE_URL.to_string());
+    let mut path = answers::answers_file(year).display().to_string();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--session" => {
+                session = Some(args.next().ok_or("Missing value for --session")?.clone())
+            }
+            "--base-url" => base_url = args.next().ok_or("Missing value for --base-url")?.clone(),
+            "--answers" => path = args.next().ok_or("Missing value for --answers")?.clone(),
+            s => positional.push(s),
+        }
+    }
+    let (day, part) = match positional.as_slice() {
+        [day, part] => (parse_day(day)?, parse_part(part)?),
+        _ => return Err("Expected <day> <part>".to_string()),
+    };
+    let session = require_session(session)?;
+    let solver = days::get(year, day)
+        .ok_or_else(|| format!("Day {} of {} is not implemented", day, year))?;
+
+    let res = runner::run(solver.as_ref(), solver.input(), &[part]).remove(0);
+    let answer = match res.answer {
+        Ok(answer) => answer,
+        Err(e) => {
+            eprintln!("Nothing submitted, {}", e);
+            return Ok(false);
+        }
+    };
+    println!("Day {} part {}: {}", day, part, answer);
+
+    let history_file = submit::history_file(year);
+    let mut history = History::load(&history_file)
+        .map_err(|e| format!("Could not read {}: {}", history_file.display(), e))?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| e.to_string())?
+        .as_secs();
+    if let Some(reason) = history.refusal(day, part, &answer, now) {
+        eprintln!("Nothing submitted, {}", reason);
+        return Ok(false);
+    }
+
+    let verdict = submit::submit(&http::System, &base_url, &session, year, day, part, &answer)
+        .map_err(|e| format!("Could not submit: {}", e))?;
+    println!("The answer is {}", verdict);
+    if verdict.is_recorded() {
+        history.record(Submission {
+            day,
+            part: part.number(),
+            time: now,
+            verdict,
+            answer: answer.clone(),
+        });
+        history
+            .save(&history_file)
+            .map_err(|e| format!("Could not write {}: {}", history_file.display(), e))?;
+    }
+
+    if verdict == Verdict::Right {
+        let mut answers = load_answers(&path)?;
+        answers.set(day, part, "input", &answer);
+        answers
+            .save(&path)
+            .map_err(|e| format!("Could not write {}: {}", path, e))?;
+    }
+    Ok(verdict == Verdict::Right)
+}
+
+/// Re-runs a day and its tests whenever one of its files changes, until
+/// interrupted.
+///
+/// Every round goes through cargo, since this binary has the solver as it
+/// was when it was built.
+fn cmd_watch(year: u16, args: &[String]) -> Result<bool, String> {
+    let year = registered(year)?;
+    let mut day: Option<u8> = None;
+    let mut interval = DEFAULT_INTERVAL;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--interval" => {
+                let ms = args.next().ok_or("Missing value for --interval")?;
+                let ms = ms
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|ms| *ms > 0)
+                    .ok_or_else(|| format!("Invalid interval '{}'", ms))?;
+                interval = Duration::from_millis(ms);
+            }
+            s if day.is_none() => day = Some(parse_day(s)?),
+            s => return Err(format!("Unexpected argument '{}'", s)),
+        }
+    }
+    let day = day.ok_or("Missing day")?;
+
+    let mut watcher =
+        Watcher::new(year.dir, day).map_err(|e| format!("Could not read {}: {}", year.dir, e))?;
+    if watcher.files().next().is_none() {
+        return Err(format!("No files of day {} in {}", day, year.dir));
+    }
+    for file in watcher.files() {
+        println!("Watching {}", file.display());
+    }
+
+    let mut last = Answers::default();
+    loop {
+        println!();
+        if let Some(answers) = watch_round(year.year, day, &last) {
+            last = answers;
+        }
+
+        println!("\nWaiting for changes...");
+        loop {
+            thread::sleep(interval);
+            let changed = watcher
+                .changed()
+                .map_err(|e| format!("Could not read {}: {}", year.dir, e))?;
+            if !changed.is_empty() {
+                for file in changed.iter() {
+                    println!("Changed {}", file.display());
+                }
+                break;
+            }
+        }
+    }
+}
+
+/// Builds and runs `day` of `year`, shows how its answers differ from
+/// `previous` and runs its tests. Returns the answers, `None` if it did not
+/// build.
+fn watch_round(year: u16, day: u8, previous: &Answers) -> Option<Answers> {
+    // The build must not replace the binary running this, which cannot be
+    // done while it runs on Windows
+    let cargo = |command: &str, args: &[&str]| {
+        let mut cmd = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+        cmd.current_dir(env!("CARGO_MANIFEST_DIR"))
+            .args([command, "--target-dir", WATCH_TARGET_DIR])
+            .args(args);
+        cmd
+    };
+    let year_arg = year.to_string();
+    let day_arg = day.to_string();
+
+    match cargo("build", &["--quiet", "--bin", "aoc"]).status() {
+        Ok(status) if status.success() => {}
+        Ok(_) => {
+            println!("Build failed");
+            return None;
+        }
+        Err(e) => {
+            eprintln!("Could not run cargo: {}", e);
+            return None;
+        }
+    }
+
+    // The embedded input, then the named inputs recorded for the day
+    let named = input::named_dir(year, day);
+    let named_arg = named.display().to_string();
+    let mut runs = vec![vec![]];
+    if named.is_dir() {
+        runs.push(vec!["--inputs", &named_arg]);
+    }
+
+    let mut answers = Answers::default();
+    for extra in runs {
+        let mut args = vec![
+            "--quiet", "--bin", "aoc", "--", "run", &day_arg, "--year", &year_arg, "--format",
+            "answers",
+        ];
+        args.extend(extra);
+        // Failed parts are reported on stderr by the child
+        let run = cargo("run", &args).stderr(Stdio::inherit()).output();
+        let output = match run {
+            Ok(output) => output,
+            Err(e) => {
+                eprintln!("Could not run cargo: {}", e);
+                return None;
+            }
+        };
+        match Answers::parse(&String::from_utf8_lossy(&output.stdout)) {
+            Ok(run) => {
+                for ((day, part, input), answer) in run.iter() {
+                    let part = Part::from_number(*part).expect("parsed parts are 1 or 2");
+                    answers.set(*day, part, input, answer);
+                }
+            }
+            Err(e) => eprintln!("Unexpected output of run, {}", e),
+        }
+    }
+    for line in watch::describe_changes(previous, &answers) {
+        println!("{}", line);
+    }
+
+    let filter = format!("days::y{}::day{:02}::", year, day);
+    match cargo("test", &["--quiet", "--lib", &filter]).status() {
+        Ok(status) if status.success() => println!("Tests passed"),
+        Ok(_) => println!("Tests FAILED"),
+        Err(e) => eprintln!("Could not run cargo: {}", e),
+    }
+
+    Some(answers)
+}
+
+/// Shows a dashboard of the days of a year on the terminal, checking their
+/// answers against the answers file. The last timings are kept in the cache
+/// directory.
+fn cmd_tui(year: u16, args: &[String]) -> Result<bool, String> {
+    let year = registered(year)?;
+    let mut path = answers::answers_file(year.year).display().to_string();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" => path = args.next().ok_or("Missing value for --answers")?.clone(),
+            s => return Err(format!("Unexpected argument '{}'", s)),
+        }
+    }
+
+    let answers = load_answers(&path)?;
+    tui::run(year, &answers, &tui::timings_file(year.year))
+        .map_err(|e| format!("Could not run the dashboard: {}", e))?;
+    Ok(true)
+}
+
+/// Table rows for answer checks, with the actual answer and a status.
+fn check_rows(checks: &[Check]) -> Vec<Vec<String>> {
+    checks
+        .iter()
+        .map(|c| {
+            let (actual, status) = match &c.actual {
+                Ok(actual) if c.passed() => (actual.clone(), "ok"),
+                Ok(actual) if c.is_pending() => (actual.clone(), "pending"),
+                Err(e) if c.is_pending() => (e.to_string(), "pending"),
+                Ok(actual) => (actual.clone(), "MISMATCH"),
+                Err(e) => (e.to_string(), "FAILED"),
+            };
+            vec![
+                c.day.to_string(),
+                c.part.to_string(),
+                c.input.clone(),
+                c.expected.clone(),
+                actual,
+                status.to_string(),
+            ]
+        })
+        .collect()
+}
+
+fn describe_over_budget(r: &PartResult, over: Duration) -> String {
+    format!(
+        "{}: {} over the budget of {}",
+        r.status,
+        format_duration(over),
+        format_duration(r.budget)
+    )
+}
+
+fn parse_format(value: Option<&String>) -> Result<Format, String> {
+    match value.map(String::as_str) {
+        Some("table") => Ok(Format::Table),
+        Some("json") => Ok(Format::Json),
+        Some("answers") => Ok(Format::Answers),
+        Some(other) => Err(format!("Unknown format '{}'", other)),
+        None => Err("Missing value for --format".to_string()),
+    }
+}
+
+/// The session token given, if it is not blank.
+fn require_session(session: Option<String>) -> Result<String, String> {
+    session
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| "Missing session token, pass --session or set AOC_SESSION".to_string())
+}
+
+fn load_answers(path: &str) -> Result<Answers, String> {
+    Answers::load(path).map_err(|e| format!("Could not read {}: {}", path, e))
+}
+
+fn parse_day(s: &str) -> Result<u8, String> {
+    s.parse::<u8>().map_err(|_| format!("Invalid day '{}'", s))
+}
+
+fn parse_part(s: &str) -> Result<Part, String> {
+    s.parse::<u8>()
+        .ok()
+        .and_then(Part::from_number)
+        .ok_or_else(|| format!("Invalid part '{}'", s))
+}
+
+/// Takes the `--year` option out of the arguments, the year defaults to
+/// `AOC_YEAR` and then to the latest year.
+fn take_year(mut args: Vec<String>) -> Result<(u16, Vec<String>), String> {
+    let mut year = match env::var("AOC_YEAR") {
+        Ok(year) => parse_year(&year)?,
+        Err(_) => days::latest().year,
+    };
+    if let Some(i) = args.iter().position(|a| a == "--year" || a == "-y") {
+        let value = args.get(i + 1).ok_or("Missing value for --year")?;
+        year = parse_year(value)?;
+        args.drain(i..i + 2);
+    }
+    Ok((year, args))
+}
+
+fn parse_year(s: &str) -> Result<u16, String> {
+    s.parse::<u16>()
+        .ok()
+        .filter(|year| *year >= 2015)
+        .ok_or_else(|| format!("Invalid year '{}'", s))
+}
+
+fn registered(year: u16) -> Result<&'static Year, String> {
+    days::year(year).ok_or_else(|| {
+        format!(
+            "Year {} has no days, start it with 'aoc new <day> --year {}'",
+            year, year
+        )
+    })
+}
+
+fn select_days(year: &Year, which: &str) -> Result<Vec<Box<dyn Solver>>, String> {
+    if which == "all" {
+        return Ok(year.all());
+    }
+
+    let day = parse_day(which)?;
+    match year.get(day) {
+        Some(solver) => Ok(vec![solver]),
+        None => Err(format!("Day {} of {} is not implemented", day, year.year)),
+    }
+}
+
+/// Prints rows as a table, numbers are right aligned.
+fn print_table(header: &[&str], rows: Vec<Vec<String>>) {
+    let mut widths: Vec<usize> = header.iter().map(|h| h.len()).collect();
+    for row in rows.iter() {
+        for (w, cell) in widths.iter_mut().zip(row.iter()) {
+            *w = (*w).max(cell.chars().count());
+        }
+    }
+
+    let print_row = |cells: &[String]| {
+        let cells: Vec<String> = cells
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, w)| {
+                if !cell.is_empty() && cell.chars().all(|c| c.is_ascii_digit()) {
+                    format!("{:>w$}", cell, w = w)
+                } else {
+                    format!("{:<w$}", cell, w = w)
+                }
+            })
+            .collect();
+        println!("{}", cells.join(" | ").trim_end());
+    };
+
+    print_row(&header.iter().map(|h| h.to_string()).collect::<Vec<_>>());
+    let rule: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+    println!("{}", rule.join("-+-"));
+    for row in rows.iter() {
+        print_row(row);
+    }
+}
@@ -0,0 +1,46 @@
+use crate::Solver;
+
+/// Where the years live, each in a `yNNNN` directory with its solvers and
+/// their inputs.
+pub const DAYS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/days");
+
+pub mod y2020;
+
+/// The solvers of one year.
+pub struct Year {
+    pub year: u16,
+    /// Where the solvers and their inputs live.
+    pub dir: &'static str,
+    solvers: fn() -> Vec<Box<dyn Solver>>,
+}
+
+impl Year {
+    pub const fn new(year: u16, dir: &'static str, solvers: fn() -> Vec<Box<dyn Solver>>) -> Self {
+        Year { year, dir, solvers }
+    }
+
+    /// All implemented days, in calendar order.
+    pub fn all(&self) -> Vec<Box<dyn Solver>> {
+        (self.solvers)()
+    }
+
+    pub fn get(&self, day: u8) -> Option<Box<dyn Solver>> {
+        self.all().into_iter().find(|s| s.day() == day)
+    }
+}
+
+/// All years, oldest first.
+pub const YEARS: &[Year] = &[Year::new(2020, y2020::DIR, y2020::all)];
+
+pub fn year(year: u16) -> Option<&'static Year> {
+    YEARS.iter().find(|y| y.year == year)
+}
+
+/// The year used when none is given.
+pub fn latest() -> &'static Year {
+    YEARS.last().expect("at least one year is registered")
+}
+
+pub fn get(year: u16, day: u8) -> Option<Box<dyn Solver>> {
+    self::year(year).and_then(|y| y.get(day))
+}
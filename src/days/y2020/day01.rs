@@ -0,0 +1,208 @@
+/*
+--- Day 1: Report Repair ---
+
+After saving Christmas five years in a row, you've decided to take a vacation at a nice resort on a tropical island. Surely, Christmas will go on without you.
+
+The tropical island has its own currency and is entirely cash-only. The gold coins used there have a little picture of a starfish; the locals just call them stars. None of the currency exchanges seem to have heard of them, but somehow, you'll need to find fifty of these coins by the time you arrive so you can pay the deposit on your room.
+
+To save your vacation, you need to get all fifty stars by December 25th.
+
+Collect stars by solving puzzles. Two puzzles will be made available on each day in the Advent calendar; the second puzzle is unlocked when you complete the first. Each puzzle grants one star. Good luck!
+
+--- Part One ---
+
+Before you leave, the Elves in accounting just need you to fix your expense report (your puzzle input); apparently, something isn't quite adding up.
+
+Specifically, they need you to find the two entries that sum to 2020 and then multiply those two numbers together.
+
+For example, suppose your expense report contained the following:
+
+```example part1=514579 part2=241861950
+1721
+979
+366
+299
+675
+1456
+```
+
+In this list, the two entries that sum to 2020 are 1721 and 299. Multiplying them together produces 1721 * 299 = 514579, so the correct answer is 514579.
+
+Of course, your expense report is much larger. Find the two entries that sum to 2020; what do you get if you multiply them together?
+*/
+
+/*
+--- Part Two ---
+
+The Elves in accounting are thankful for your help; one of them even offers you a starfish coin they had left over from a past vacation. They offer you a second one if you can find three numbers in your expense report that meet the same criteria.
+
+Using the above example again, the three entries that sum to 2020 are 979, 366, and 675. Multiplying them together produces the answer, 241861950.
+
+In your expense report, what is the product of the three entries that sum to 2020?
+*/
+
+use crate::input::parse_lines;
+use crate::{AocError, Result, Solution};
+
+static INPUT: &str = include_str!("day01-input.txt");
+
+fn find_sum_pair(nums: &[u32], target: u32) -> Option<(usize, usize)> {
+    let mut i = 0;
+    let mut j = nums.len().checked_sub(1)?;
+
+    // Widened, two valid entries can add up to more than a u32 holds
+    let target = u64::from(target);
+    while i < j {
+        let sum = u64::from(nums[i]) + u64::from(nums[j]);
+        if sum == target {
+            return Some((i, j));
+        } else if sum > target {
+            j -= 1;
+        } else {
+            // sum < target
+            i += 1;
+        }
+    }
+
+    None
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const INPUT: &'static str = INPUT;
+
+    type Input = Vec<u32>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(&self, input: &str) -> Result<Vec<u32>> {
+        let mut expenses = parse_lines(input, |l| {
+            l.trim().parse::<u32>().map_err(|e| {
+                AocError::new(Self::DAY, format!("invalid expense '{}': {}", l, e)).at_column(1)
+            })
+        })?;
+        expenses.sort_unstable();
+        Ok(expenses)
+    }
+
+    fn part1(&self, sorted: &Vec<u32>) -> Result<u32> {
+        let (i, j) = find_sum_pair(sorted, 2020)
+            .ok_or_else(|| AocError::new(Self::DAY, "no two entries sum to 2020"))?;
+        Ok(sorted[i] * sorted[j])
+    }
+
+    fn part2(&self, sorted: &Vec<u32>) -> Result<u32> {
+        // The other two entries are searched after `v`, so that no entry is
+        // used twice
+        sorted
+            .iter()
+            .enumerate()
+            .filter_map(|(k, v)| Some((k, v, 2020_u32.checked_sub(*v)?)))
+            .find_map(|(k, v, rest)| {
+                let others = &sorted[k + 1..];
+                find_sum_pair(others, rest).map(|(i, j)| others[i] * others[j] * v)
+            })
+            .ok_or_else(|| AocError::new(Self::DAY, "no three entries sum to 2020"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::Rng;
+
+    #[test]
+    fn test_example() {
+        let expenses = Day01.parse("1721\n979\n366\n299\n675\n1456").unwrap();
+        assert_eq!(Ok(514579), Day01.part1(&expenses));
+        assert_eq!(Ok(241861950), Day01.part2(&expenses));
+    }
+
+    #[test]
+    fn test_bad_input() {
+        let err = Day01.parse("1721\n97x9\n366").unwrap_err();
+        assert_eq!(Some(2), err.line);
+        assert!(Day01.part1(&vec![1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn test_small_inputs() {
+        assert_eq!(None, find_sum_pair(&[], 2020));
+        assert_eq!(None, find_sum_pair(&[2020], 2020));
+        assert!(Day01.part1(&vec![]).is_err());
+        assert!(Day01.part2(&vec![1010]).is_err());
+        // 1010 + 1010 uses the same entry twice, 500 + 500 + 1020 does not
+        assert!(Day01.part1(&vec![1010]).is_err());
+        assert!(Day01.part2(&vec![500, 1010]).is_err());
+        assert_eq!(Ok(255_000_000), Day01.part2(&vec![500, 500, 1020]));
+    }
+
+    #[test]
+    fn test_large_entries() {
+        let huge = u32::MAX;
+        assert_eq!(
+            Some((0, 1)),
+            find_sum_pair(&[10, 2010, huge - 1, huge], 2020)
+        );
+        assert_eq!(None, find_sum_pair(&[1, huge], 2020));
+        let expenses = Day01
+            .parse(&format!("{}\n1721\n{}\n299", huge, huge))
+            .unwrap();
+        assert_eq!(Ok(514579), Day01.part1(&expenses));
+    }
+
+    fn naive_pairs(nums: &[u32], target: u32) -> Vec<(u32, u32)> {
+        let mut pairs = Vec::new();
+        for (i, a) in nums.iter().enumerate() {
+            for b in nums[i + 1..].iter() {
+                if a + b == target {
+                    pairs.push((*a, *b));
+                }
+            }
+        }
+        pairs
+    }
+
+    #[test]
+    fn test_matches_naive_search() {
+        let mut rng = Rng::new(1);
+        for _ in 0..500 {
+            let mut nums: Vec<u32> = (0..rng.range(0, 12))
+                .map(|_| rng.range(0, 40) as u32)
+                .collect();
+            nums.sort_unstable();
+            let target = rng.range(0, 80) as u32;
+
+            let pairs = naive_pairs(&nums, target);
+            let found = find_sum_pair(&nums, target).map(|(i, j)| (nums[i], nums[j]));
+            match found {
+                Some(pair) => assert!(pairs.contains(&pair), "{:?} in {:?}", pair, nums),
+                None => assert!(pairs.is_empty(), "{:?} in {:?}", pairs, nums),
+            }
+        }
+    }
+
+    #[test]
+    fn test_part2_matches_naive_search() {
+        let mut rng = Rng::new(2);
+        for _ in 0..200 {
+            let mut nums: Vec<u32> = (0..rng.range(0, 12))
+                .map(|_| rng.range(500, 1100) as u32)
+                .collect();
+            nums.sort_unstable();
+
+            let mut products = Vec::new();
+            for (k, v) in nums.iter().enumerate() {
+                for (a, b) in naive_pairs(&nums[k + 1..], 2020_u32.saturating_sub(*v)) {
+                    products.push(a * b * v);
+                }
+            }
+            match Day01.part2(&nums) {
+                Ok(product) => assert!(products.contains(&product), "{:?}", nums),
+                Err(_) => assert!(products.is_empty(), "{:?} in {:?}", products, nums),
+            }
+        }
+    }
+}
@@ -11,9 +11,11 @@ To try to debug the problem, they have created a list (your puzzle input) of pas
 
 For example, suppose you have the following list:
 
+```example part1=2 part2=1
 1-3 a: abcde
 1-3 b: cdefg
 2-9 c: ccccccccc
+```
 
 Each line gives the password policy and then the password. The password policy indicates the lowest and highest number of times a given letter must appear for the password to be valid. For example, 1-3 a means that the password must contain a at least 1 time and at most 3 times.
 
@@ -21,17 +23,20 @@ In the above example, 2 passwords are valid. The middle password, cdefg, is not;
 
 How many passwords are valid according to their policies?
 */
+use crate::error::column;
+use crate::input::parse_lines;
+use crate::{AocError, Result, Solution};
 use regex::Regex;
 
 static INPUT: &str = include_str!("day02-input.txt");
 
 fn validate_part_1(a: u8, b: u8, c: char, password: &str) -> bool {
-    let count: u8 = password
+    let count: usize = password
         .chars()
         .map(|cur| if cur == c { 1 } else { 0 })
         .sum();
 
-    count >= a && count <= b
+    count >= a as usize && count <= b as usize
 }
 
 /*--- Part Two ---
@@ -51,43 +56,84 @@ Given the same example list from above:
 How many passwords are valid according to the new interpretation of the policies?
 */
 fn validate_part_2(i0: usize, i1: usize, c: char, password: &str) -> bool {
-    let aa = password.chars().nth(i0 - 1).unwrap() == c;
-    let bb = match password.chars().nth(i1 - 1) {
-        Some(ch) => ch == c,
-        None => false,
-    };
+    let at = |i: usize| i.checked_sub(1).and_then(|i| password.chars().nth(i)) == Some(c);
+    let aa = at(i0);
+    let bb = at(i1);
 
     xor(aa, bb)
 }
 
 fn xor(a: bool, b: bool) -> bool {
-    (a || b) && !(a == b)
+    (a || b) && a != b
 }
 
-fn main() {
-    let re = Regex::new(r"([0-9]+)-([0-9]+) ([a-z]): ([a-z]*)$").unwrap();
-
-    let mut valid_part1 = 0;
-    let mut valid_part2 = 0;
+#[derive(Debug)]
+pub struct Policy {
+    a: u8,
+    b: u8,
+    c: char,
+    password: String,
+}
 
-    for line in INPUT.lines() {
-        // min-max character:
-        let captures = re.captures(&line).unwrap();
-        let min = captures.get(1).unwrap().as_str().parse::<u8>().unwrap();
-        let max = captures.get(2).unwrap().as_str().parse::<u8>().unwrap();
-        let passchar = captures.get(3).unwrap().as_str().chars().next().unwrap();
-        let password = captures.get(4).unwrap().as_str();
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const INPUT: &'static str = INPUT;
+
+    type Input = Vec<Policy>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<Policy>> {
+        let re = Regex::new(r"^([0-9]+)-([0-9]+) ([a-z]): ([a-z]*)$").unwrap();
+
+        parse_lines(input, |line| {
+            // min-max character: password
+            let captures = re
+                .captures(line)
+                .map(|c| (c.get(1), c.get(2), c.get(3), c.get(4)));
+            let (a, b, c, password) = match captures {
+                Some((Some(a), Some(b), Some(c), Some(password))) => (a, b, c, password),
+                _ => {
+                    return Err(
+                        AocError::new(Self::DAY, "expected a line like '1-3 a: abcde'")
+                            .at_column(1),
+                    )
+                }
+            };
+
+            let number = |m: regex::Match| {
+                m.as_str().parse::<u8>().map_err(|e| {
+                    AocError::new(Self::DAY, format!("invalid number '{}': {}", m.as_str(), e))
+                        .at_column(column(line, m.as_str()))
+                })
+            };
+
+            Ok(Policy {
+                a: number(a)?,
+                b: number(b)?,
+                c: c.as_str().parse::<char>().map_err(|e| {
+                    AocError::new(Self::DAY, e.to_string()).at_column(column(line, c.as_str()))
+                })?,
+                password: password.as_str().to_string(),
+            })
+        })
+    }
 
-        if validate_part_1(min, max, passchar, &password) {
-            valid_part1 += 1;
-        }
+    fn part1(&self, policies: &Vec<Policy>) -> Result<usize> {
+        Ok(policies
+            .iter()
+            .filter(|p| validate_part_1(p.a, p.b, p.c, &p.password))
+            .count())
+    }
 
-        if validate_part_2(min as usize, max as usize, passchar, &password) {
-            valid_part2 += 1;
-        }
+    fn part2(&self, policies: &Vec<Policy>) -> Result<usize> {
+        Ok(policies
+            .iter()
+            .filter(|p| validate_part_2(p.a as usize, p.b as usize, p.c, &p.password))
+            .count())
     }
-    println!("Number of valid passwords - part 1: {}", valid_part1);
-    println!("Number of valid passwords - part 2: {}", valid_part2);
 }
 
 #[cfg(test)]
@@ -96,9 +142,18 @@ mod tests {
 
     #[test]
     fn xor_test() {
-        assert_eq!(false, xor(false, false));
-        assert_eq!(true, xor(true, false));
-        assert_eq!(true, xor(false, true));
-        assert_eq!(false, xor(true, true));
+        assert!(!xor(false, false));
+        assert!(xor(true, false));
+        assert!(xor(false, true));
+        assert!(!xor(true, true));
+    }
+
+    #[test]
+    fn test_bad_input() {
+        let err = Day02.parse("1-3 a: abcde\n1-300 b: cdefg").unwrap_err();
+        assert_eq!((Some(2), Some(3)), (err.line, err.column));
+
+        let err = Day02.parse("1-3 a: abcde\n1-3 b cdefg").unwrap_err();
+        assert_eq!((Some(2), Some(1)), (err.line, err.column));
     }
 }
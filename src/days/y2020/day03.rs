@@ -5,6 +5,7 @@ With the toboggan login problems resolved, you set off toward the airport. While
 
 Due to the local geology, trees in this area only grow on exact integer coordinates in a grid. You make a map (your puzzle input) of the open squares (.) and trees (#) you can see. For example:
 
+```example part1=7 part2=336
 ..##.......
 #...#...#..
 .#....#..#.
@@ -16,6 +17,7 @@ Due to the local geology, trees in this area only grow on exact integer coordina
 #.##...#...
 #...##....#
 .#..#...#.#
+```
 
 These aren't the only trees, though; due to something you read about once involving arboreal genetics and biome stability, the same pattern repeats to the right many times:
 
@@ -74,31 +76,52 @@ In the above example, these slopes would find 2, 7, 3, 4, and 2 tree(s) respecti
 What do you get if you multiply together the number of trees encountered on each of the listed slopes?
 */
 
+use crate::grid::Grid;
+use crate::{debug, AocError, Result, Solution};
+
 static INPUT: &str = include_str!("day03-input.txt");
 
-fn calc_hits(right: usize, down: usize) -> u32 {
-    INPUT
-        .lines()
+fn calc_hits(map: &Grid<bool>, right: usize, down: usize) -> u32 {
+    (0..map.height())
         .step_by(down)
         .enumerate()
-        .map(|(row, line)| {
-            if line.chars().nth((row * right) % line.len()).unwrap() == '#' {
-                1
-            } else {
-                0
-            }
-        })
-        .sum()
+        .filter(|(step, y)| map.get_wrapped((step * right) as isize, *y as isize) == Some(&true))
+        .count() as u32
 }
 
-fn main() {
-    let slopes: [(usize, usize); 5] = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+pub struct Day03;
 
-    let res: u32 = slopes.iter().map(|(r, d)| calc_hits(*r, *d)).product();
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const INPUT: &'static str = INPUT;
 
-    // for (r, d) in slopes.iter() {
-    //     println!("Slope{}:{}, Trees: {}", r, d, calc_hits(*r, *d));
-    // }
+    type Input = Grid<bool>;
+    type Part1 = u32;
+    type Part2 = u32;
 
-    println!("Product of trees hit: {}", res);
+    fn parse(&self, input: &str) -> Result<Grid<bool>> {
+        Grid::parse(input, |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(AocError::new(Self::DAY, "expected '.' or '#'")),
+        })
+        .map_err(|e| e.for_day(Self::DAY))
+    }
+
+    fn part1(&self, map: &Grid<bool>) -> Result<u32> {
+        Ok(calc_hits(map, 3, 1))
+    }
+
+    fn part2(&self, map: &Grid<bool>) -> Result<u32> {
+        let slopes: [(usize, usize); 5] = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
+        Ok(slopes
+            .iter()
+            .map(|&(right, down)| {
+                let hits = calc_hits(map, right, down);
+                debug!("slope right {} down {}: {} trees", right, down, hits);
+                hits
+            })
+            .product())
+    }
 }
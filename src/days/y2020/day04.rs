@@ -21,6 +21,7 @@ Passport data is validated in batch files (your puzzle input). Each passport is
 
 Here is an example batch file containing four passports:
 
+```example part1=2
 ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
 byr:1937 iyr:2017 cid:147 hgt:183cm
 
@@ -34,6 +35,7 @@ hgt:179cm
 
 hcl:#cfa07d eyr:2025 pid:166559648
 iyr:2011 ecl:brn hgt:59in
+```
 
 The first passport is valid - all eight fields are present. The second passport is invalid - it is missing hgt (the Height field).
 
@@ -84,6 +86,7 @@ pid invalid: 0123456789
 
 Here are some invalid passports:
 
+```example part2=0
 eyr:1972 cid:100
 hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
 
@@ -97,9 +100,11 @@ ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
 hgt:59cm ecl:zzz
 eyr:2038 hcl:74454a iyr:2023
 pid:3556412378 byr:2007
+```
 
 Here are some valid passports:
 
+```example part2=4
 pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
 hcl:#623a2f
 
@@ -112,12 +117,18 @@ pid:545766238 ecl:hzl
 eyr:2022
 
 iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719
+```
 
 Count the number of valid passports - those that have all required fields and valid values. Continue to treat cid as optional. In your batch file, how many passports are valid?
 */
 
+use crate::error::column;
+use crate::input::{records, Record};
+use crate::{debug, AocError, Result, Solution};
 use std::collections::HashMap;
 
+static INPUT: &str = include_str!("day04-input.txt");
+
 static REQS1: [&str; 7] = [
     "byr", // (Birth Year)
     "iyr", // (Issue Year)
@@ -129,27 +140,28 @@ static REQS1: [&str; 7] = [
            //    "cid", // (Country ID) // not needed
 ];
 
-fn extract_parts(pass: &&str) -> HashMap<String, String> {
-    pass.split_whitespace()
-        .map(|field| {
-            let mut parts = field.split(':');
-            let a = parts.next().unwrap().to_string();
-            let b = parts.next().unwrap().to_string();
-            (a, b)
-        })
-        .collect()
-}
-
-fn is_valid_part1(pass: &&str) -> bool {
-    let parts = extract_parts(pass);
-
-    for r in REQS1.iter() {
-        if parts.contains_key(*r) == false {
-            return false;
+type Passport = HashMap<String, String>;
+
+fn extract_parts(pass: &Record) -> Result<Passport> {
+    let mut parts = HashMap::new();
+    for (line_number, line) in pass.lines() {
+        for field in line.split_whitespace() {
+            let (key, value) = field.split_once(':').ok_or_else(|| {
+                AocError::new(
+                    Day04::DAY,
+                    format!("expected 'key:value', found '{}'", field),
+                )
+                .at(line_number, column(line, field))
+            })?;
+            parts.insert(key.to_string(), value.to_string());
         }
     }
+    Ok(parts)
+}
 
-    true
+/// The first required field missing from `parts`, if any.
+fn missing_field(parts: &Passport) -> Option<&'static str> {
+    REQS1.iter().find(|r| !parts.contains_key(**r)).copied()
 }
 
 /*
@@ -164,24 +176,33 @@ ecl (Eye Color) - exactly one of: amb blu brn gry grn hzl oth.
 pid (Passport ID) - a nine-digit number, including leading zeroes.
 cid (Country ID) - ignored, missing or not.*/
 
-fn is_valid_part2(pass: &&str) -> bool {
-    let parts: HashMap<String, String> = extract_parts(pass);
-    test_birth_year(&parts)
-        && test_issue_year(&parts)
-        && test_expiration_year(&parts)
-        && test_height(&parts)
-        && test_hair_color(&parts)
-        && test_eye_color(&parts)
-        && test_pass_id(&parts)
+type Rule = fn(&Passport) -> bool;
+
+static RULES2: [(&str, Rule); 7] = [
+    ("byr", test_birth_year),
+    ("iyr", test_issue_year),
+    ("eyr", test_expiration_year),
+    ("hgt", test_height),
+    ("hcl", test_hair_color),
+    ("ecl", test_eye_color),
+    ("pid", test_pass_id),
+];
+
+/// The field of the first rule `parts` breaks, if any.
+fn failed_rule(parts: &Passport) -> Option<&'static str> {
+    RULES2
+        .iter()
+        .find(|(_, rule)| !rule(parts))
+        .map(|(field, _)| *field)
 }
 
-fn test_year(s: &String, min: u32, max: u32) -> bool {
+fn test_year(s: &str, min: u32, max: u32) -> bool {
     if s.len() != 4 {
         false
     } else if let Ok(year) = s.parse::<u32>() {
         year >= min && year <= max
     } else {
-        panic!("asdf");
+        false
     }
 }
 
@@ -213,7 +234,7 @@ fn test_height_helper(s: Option<&str>, min: u32, max: u32) -> bool {
     match s {
         Some(h) => match h.parse::<u32>() {
             Ok(h) => h >= min && h <= max,
-            _ => panic!("asdf"),
+            Err(_) => false,
         },
         None => false,
     }
@@ -237,8 +258,8 @@ fn test_height(map: &HashMap<String, String>) -> bool {
     }
 }
 
-fn is_color(s: &String) -> bool {
-    if s.len() == 7 && s.starts_with("#") {
+fn is_color(s: &str) -> bool {
+    if s.len() == 7 && s.starts_with('#') {
         s.chars().skip(1).all(|c: char| c.is_ascii_hexdigit())
     } else {
         false
@@ -274,23 +295,80 @@ fn test_eye_color(map: &HashMap<String, String>) -> bool {
 fn test_pass_id(map: &HashMap<String, String>) -> bool {
     //pid (Passport ID) - a nine-digit number, including leading zeroes.
     match map.get("pid") {
-        Some(s) => {
-            if s.len() == 9 {
-                s.chars().all(|c: char| c.is_ascii_digit())
-            } else {
-                false
-            }
-        }
-        None => false,
+        Some(s) if s.len() == 9 => s.chars().all(|c: char| c.is_ascii_digit()),
+        _ => false,
     }
 }
 
-fn main() {
-    let input: String = include_str!("day04-input.txt").replace("\r\n", "\n");
-    let passports = input.split("\n\n");
-    let part1 = passports.clone().filter(is_valid_part1).count();
-    let part2 = passports.clone().filter(is_valid_part2).count();
-    println!("Number of passports: : {}", passports.count());
-    println!("Valid passports - part 1: {}", part1);
-    println!("Valid passports - part 2: {}", part2);
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const INPUT: &'static str = INPUT;
+
+    type Input = Vec<Passport>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<Passport>> {
+        records(input).iter().map(extract_parts).collect()
+    }
+
+    fn part1(&self, passports: &Vec<Passport>) -> Result<usize> {
+        Ok(passports
+            .iter()
+            .enumerate()
+            .filter(|(i, p)| match missing_field(p) {
+                Some(field) => {
+                    debug!("passport {}: missing {}", i + 1, field);
+                    false
+                }
+                None => true,
+            })
+            .count())
+    }
+
+    fn part2(&self, passports: &Vec<Passport>) -> Result<usize> {
+        Ok(passports
+            .iter()
+            .enumerate()
+            .filter(|(i, p)| match failed_rule(p) {
+                Some(field) => {
+                    match p.get(field) {
+                        Some(value) => debug!("passport {}: invalid {} '{}'", i + 1, field, value),
+                        None => debug!("passport {}: missing {}", i + 1, field),
+                    }
+                    false
+                }
+                None => true,
+            })
+            .count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bad_values_are_invalid() {
+        let passports = Day04
+            .parse("byr:19x0 iyr:2012 eyr:2020 hgt:1x0cm hcl:#888785 ecl:blu pid:093154719")
+            .unwrap();
+        assert_eq!(Ok(1), Day04.part1(&passports));
+        assert_eq!(Ok(0), Day04.part2(&passports));
+    }
+
+    #[test]
+    fn test_bad_field() {
+        let err = Day04
+            .parse("ecl:gry pid:860033327\n\niyr:2013 ecl:amb\nhcl#cfa07d byr:1929")
+            .unwrap_err();
+        assert_eq!((Some(4), Some(1)), (err.line, err.column));
+
+        let err = Day04
+            .parse("ecl:gry\r\n\r\n  \r\n\r\niyr:2013 ecl:amb\r\nhcl#cfa07d\r\n")
+            .unwrap_err();
+        assert_eq!((Some(6), Some(1)), (err.line, err.column));
+    }
 }
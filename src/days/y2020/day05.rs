@@ -33,12 +33,28 @@ So, decoding FBFBBFFRLR reveals that it is the seat at row 44, column 5.
 
 Every seat also has a unique seat ID: multiply the row by 8, then add the column. In this example, the seat has ID 44 * 8 + 5 = 357.
 
+```example part1=357
+FBFBBFFRLR
+```
+
 Here are some other boarding passes:
 
     BFFFBBFRRR: row 70, column 7, seat ID 567.
     FFFBBBFRRR: row 14, column 7, seat ID 119.
     BBFFBBFRLL: row 102, column 4, seat ID 820.
 
+```example part1=567
+BFFFBBFRRR
+```
+
+```example part1=119
+FFFBBBFRRR
+```
+
+```example part1=820
+BBFFBBFRLL
+```
+
 As a sanity check, look through your list of boarding passes. What is the highest seat ID on a boarding pass?
 
 */
@@ -53,67 +69,117 @@ Your seat wasn't at the very front or back, though; the seats with IDs +1 and -1
 What is the ID of your seat?
 */
 
+use crate::grid::Grid;
+use crate::input::parse_lines;
+use crate::json::Json;
+use crate::solution::{Answer, Details};
+use crate::{trace, AocError, Result, Solution};
+use std::fmt;
+
 static INPUT: &str = include_str!("day05-input.txt");
 
-fn main() {
-    let mut used_seats: [bool; 109 * 8] = [false; 109 * 8]; // rows ranges from 9 to 108
+/// A seat, displayed as its seat ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Seat {
+    pub row: u8,
+    pub col: u8,
+    pub id: u16,
+}
 
-    let mut max_seat_id = 0;
-    let mut min_row = 128;
-    let mut max_row = 0;
+impl fmt::Display for Seat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id)
+    }
+}
 
-    for line in INPUT.lines() {
-        let (row, _, seat_id) = parse_seat(line);
-        min_row = min_row.min(row);
-        max_row = max_row.max(row);
-        max_seat_id = max_seat_id.max(seat_id);
-        used_seats[seat_id as usize] = true;
+impl Answer for Seat {
+    fn details(&self) -> Details {
+        vec![("row", Json::from(self.row)), ("col", Json::from(self.col))]
     }
+}
 
-    // Print seat plan
-    // for row in used_seats.chunks(8) {
-    //     for seat in row.iter() {
-    //         print!("{}", (if *seat { "#" } else { "." }));
-    //     }
-    //     println!("");
-    // }
-
-    let a = used_seats.get(0..).unwrap().iter();
-    let b = used_seats.get(1..).unwrap().iter();
-    let c = used_seats.get(2..).unwrap().iter();
-    let your_seat_id = a
-        .zip(b)
-        .zip(c)
-        .enumerate()
-        .find(|(_, ((pre, cur), next))| **pre && !**cur && **next)
-        .unwrap()
-        .0
-        + 1;
-
-    println!("Your seat id: {:?}", your_seat_id);
-    println!("Max seat id: {}", max_seat_id);
-    println!("Min:Max row id: {}:{}", min_row, max_row);
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const INPUT: &'static str = INPUT;
+
+    type Input = Vec<(u8, u8, u16)>;
+    type Part1 = Seat;
+    type Part2 = Seat;
+
+    fn parse(&self, input: &str) -> Result<Vec<(u8, u8, u16)>> {
+        parse_lines(input, parse_seat)
+    }
+
+    fn part1(&self, seats: &Vec<(u8, u8, u16)>) -> Result<Seat> {
+        seats
+            .iter()
+            .max_by_key(|(_, _, seat_id)| *seat_id)
+            .map(|&(row, col, id)| Seat { row, col, id })
+            .ok_or_else(|| AocError::new(Self::DAY, "no boarding passes"))
+    }
+
+    fn part2(&self, seats: &Vec<(u8, u8, u16)>) -> Result<Seat> {
+        let mut plan = Grid::new(8, 128, false).map_err(|e| e.for_day(Self::DAY))?;
+
+        for (line, (row, col, seat_id)) in seats.iter().enumerate() {
+            let seat = plan.get_mut(*col as isize, *row as isize).ok_or_else(|| {
+                AocError::new(
+                    Self::DAY,
+                    format!("seat id {} is outside the plan", seat_id),
+                )
+                .on_line(line + 1)
+            })?;
+            *seat = true;
+        }
+
+        trace!(
+            "seat plan:\n{}",
+            plan.render(|seat| if *seat { '#' } else { '.' })
+        );
+
+        let used_seats: Vec<bool> = plan.iter().map(|(_, seat)| *seat).collect();
+        used_seats
+            .windows(3)
+            .position(|w| w[0] && !w[1] && w[2])
+            .map(|i| Seat {
+                row: ((i + 1) / 8) as u8,
+                col: ((i + 1) % 8) as u8,
+                id: (i + 1) as u16,
+            })
+            .ok_or_else(|| AocError::new(Self::DAY, "no free seat between two taken seats"))
+    }
 }
 
 fn parse_bin(s: &str, on: char) -> u8 {
     let exp = s.len() as u32 - 1;
     s.chars()
         .enumerate()
-        .map(|(i, c)| {
-            if c == on {
-                (2 as u8).pow(exp - i as u32)
-            } else {
-                0
-            }
-        })
+        .map(|(i, c)| if c == on { 2_u8.pow(exp - i as u32) } else { 0 })
         .sum::<u8>()
 }
 
-fn parse_seat(s: &str) -> (u8, u8, u16) {
-    let row = parse_bin(&s.get(0..7).unwrap(), 'B');
-    let col = parse_bin(&s.get(7..).unwrap(), 'R');
+fn parse_seat(s: &str) -> Result<(u8, u8, u16)> {
+    if let Some((i, _)) = s
+        .chars()
+        .enumerate()
+        .find(|(i, c)| !matches!((i, c), (0..=6, 'F' | 'B') | (7..=9, 'L' | 'R')))
+    {
+        let expected = if i < 7 { "'F' or 'B'" } else { "'L' or 'R'" };
+        return Err(AocError::new(Day05::DAY, format!("expected {}", expected)).at_column(i + 1));
+    }
+    if s.len() != 10 {
+        return Err(AocError::new(
+            Day05::DAY,
+            format!("boarding pass has {} characters, expected 10", s.len()),
+        ));
+    }
+
+    let row = parse_bin(&s[0..7], 'B');
+    let col = parse_bin(&s[7..], 'R');
 
-    (row, col, row as u16 * 8 + col as u16)
+    Ok((row, col, row as u16 * 8 + col as u16))
 }
 
 #[cfg(test)]
@@ -135,24 +201,32 @@ mod tests {
         const C: &str = "BBFFBBFRLL";
 
         {
-            let (row, col, seat_id) = parse_seat(A);
+            let (row, col, seat_id) = parse_seat(A).unwrap();
             assert_eq!(row, 70);
             assert_eq!(col, 7);
             assert_eq!(seat_id, 567);
         }
 
         {
-            let (row, col, seat_id) = parse_seat(B);
+            let (row, col, seat_id) = parse_seat(B).unwrap();
             assert_eq!(row, 14);
             assert_eq!(col, 7);
             assert_eq!(seat_id, 119);
         }
 
         {
-            let (row, col, seat_id) = parse_seat(C);
+            let (row, col, seat_id) = parse_seat(C).unwrap();
             assert_eq!(row, 102);
             assert_eq!(col, 4);
             assert_eq!(seat_id, 820);
         }
     }
+
+    #[test]
+    fn test_bad_seats() {
+        let err = parse_seat("BFFFBBFRRX").unwrap_err();
+        assert_eq!(Some(10), err.column);
+        assert!(parse_seat("BFFFBBFRR").is_err());
+        assert!(parse_seat("BFFFBBFRRRR").is_err());
+    }
 }
@@ -15,6 +15,7 @@ In this group, there are 6 questions to which anyone answered "yes": a, b, c, x,
 
 Another group asks for your help, then another, and eventually you've collected answers from every group on the plane (your puzzle input). Each group's answers are separated by a blank line, and within each group, each person's answers are on a single line. For example:
 
+```example part1=11 part2=6
 abc
 
 a
@@ -30,6 +31,7 @@ a
 a
 
 b
+```
 
 This list represents answers from five groups:
 
@@ -84,6 +86,8 @@ For each group, count the number of questions to which everyone answered "yes".
 
 */
 
+use crate::input::records;
+use crate::{AocError, Result, Solution};
 use std::collections::HashSet;
 
 static INPUT: &str = include_str!("day06-input.txt");
@@ -100,23 +104,11 @@ fn count_yeses(group: &str) -> usize {
         .len()
 }
 
-fn count_helper<F>(input: &str, f: F) -> usize
+fn count_helper<F>(groups: &[String], f: F) -> usize
 where
     F: Fn(&str) -> usize,
 {
-    input.replace("\r\n", "\n").split("\n\n").map(f).sum()
-}
-
-fn count_total_yeses(input: &str) -> usize {
-    count_helper(input, count_yeses)
-}
-
-fn count_all_total_yeses(input: &str) -> usize {
-    input
-        .replace("\r\n", "\n")
-        .split("\n\n")
-        .map(count_all_yeses)
-        .sum()
+    groups.iter().map(|g| f(g)).sum()
 }
 
 fn count_all_yeses(group: &str) -> usize {
@@ -131,16 +123,49 @@ fn count_all_yeses(group: &str) -> usize {
         .len()
 }
 
-fn main() {
-    println!("Total - part 1: {}", count_total_yeses(INPUT));
-    println!("Total - part 2: {}", count_all_total_yeses(INPUT));
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const INPUT: &'static str = INPUT;
+
+    type Input = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<String>> {
+        records(input)
+            .iter()
+            .map(|group| {
+                for (line, person) in group.lines() {
+                    if let Some(col) = person.chars().position(|c| !is_answer(&c)) {
+                        return Err(
+                            AocError::new(Self::DAY, "expected answers a to z").at(line, col + 1)
+                        );
+                    }
+                }
+                Ok(group.text())
+            })
+            .collect()
+    }
+
+    fn part1(&self, groups: &Vec<String>) -> Result<usize> {
+        Ok(count_helper(groups, count_yeses))
+    }
+
+    fn part2(&self, groups: &Vec<String>) -> Result<usize> {
+        Ok(count_helper(groups, count_all_yeses))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generate::Rng;
 
     static TEST_INPUT: &str = include_str!("day06-test-input.txt");
+    // Few letters so that groups share answers
+    const LETTERS: [char; 5] = ['a', 'b', 'c', 'x', 'z'];
 
     #[test]
     fn group_parsing() {
@@ -152,17 +177,52 @@ mod tests {
 
     #[test]
     fn group_summing() {
-        assert_eq!(11, count_total_yeses(TEST_INPUT));
+        assert_eq!(Ok(11), Day06.part1(&Day06.parse(TEST_INPUT).unwrap()));
     }
 
     #[test]
     fn group_all_summing() {
-        assert_eq!(6, count_all_total_yeses(TEST_INPUT));
+        assert_eq!(Ok(6), Day06.part2(&Day06.parse(TEST_INPUT).unwrap()));
+    }
+
+    #[test]
+    fn test_bad_input() {
+        let err = Day06.parse("abc\n\na\nB\nc").unwrap_err();
+        assert_eq!((Some(4), Some(1)), (err.line, err.column));
+
+        let groups = Day06.parse("abc \r\n\r\n \r\na\r\nb\r\n\r\n").unwrap();
+        assert_eq!(vec!["abc", "a\nb"], groups);
+    }
+
+    fn naive_count(group: &str, everyone: bool) -> usize {
+        let people: Vec<&str> = group.lines().collect();
+        ('a'..='z')
+            .filter(|c| {
+                let answered = |person: &&str| person.contains(*c);
+                if everyone {
+                    people.iter().all(answered)
+                } else {
+                    people.iter().any(answered)
+                }
+            })
+            .count()
     }
 
     #[test]
-    fn test_real_data() {
-        assert_eq!(6351, count_total_yeses(INPUT));
-        assert_eq!(3143, count_all_total_yeses(INPUT));
+    fn test_matches_naive_count() {
+        let mut rng = Rng::new(6);
+        for _ in 0..500 {
+            let group: Vec<String> = (0..rng.range(1, 6))
+                .map(|_| (0..rng.range(1, 8)).map(|_| rng.pick(&LETTERS)).collect())
+                .collect();
+            let group = group.join("\n");
+            assert_eq!(naive_count(&group, false), count_yeses(&group), "{}", group);
+            assert_eq!(
+                naive_count(&group, true),
+                count_all_yeses(&group),
+                "{}",
+                group
+            );
+        }
     }
 }
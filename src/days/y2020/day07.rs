@@ -0,0 +1,321 @@
+/*
+--- Day 7: Handy Haversacks ---
+
+You land at the regional airport in time for your next flight. In fact, it looks like you'll even have time to grab some food: all flights are currently delayed due to issues in luggage processing.
+
+Due to recent aviation regulations, many rules (your puzzle input) are being enforced about bags and their contents; bags must be color-coded and must contain specific quantities of other color-coded bags. Apparently, nobody responsible for these regulations considered how long they would take to enforce!
+
+For example, consider the following rules:
+
+```example part1=4 part2=32
+light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.
+```
+
+These rules specify the required contents for 9 bag types. In this example, every faded blue bag is empty, every vibrant plum bag contains 11 bags (5 faded blue and 6 dotted black), and so on.
+
+You have a shiny gold bag. If you wanted to carry it in at least one other bag, how many different bag colors would be valid for the outermost bag? (In other words: how many colors can, eventually, contain at least one shiny gold bag?)
+
+In the above rules, the following options would be available to you:
+
+    A bright white bag, which can hold your shiny gold bag directly.
+    A muted yellow bag, which can hold your shiny gold bag directly, plus some other bags.
+    A dark orange bag, which can hold bright white and muted yellow bags, either of which could then hold your shiny gold bag.
+    A light red bag, which can hold bright white and muted yellow bags, either of which could then hold your shiny gold bag.
+
+So, in this example, the number of bag colors that can eventually contain at least one shiny gold bag is 4.
+
+How many bag colors can eventually contain at least one shiny gold bag? (The list of rules is quite long; make sure you get all of it.)
+
+*/
+
+/*
+--- Part Two ---
+
+It's getting pretty expensive to fly these days - not because of ticket prices, but because of the ridiculous number of bags you need to buy!
+
+Consider again your shiny gold bag and the rules from the above example:
+
+    faded blue bags contain 0 other bags.
+    dotted black bags contain 0 other bags.
+    vibrant plum bags contain 11 other bags: 5 faded blue bags and 6 dotted black bags.
+    dark olive bags contain 7 other bags: 3 faded blue bags and 4 dotted black bags.
+
+So, a single shiny gold bag must contain 1 dark olive bag (and the 7 bags within it) plus 2 vibrant plum bags (and the 11 bags within each of those): 1 + 1*7 + 2 + 2*11 = 32 bags!
+
+Of course, the actual rules have a small chance of going several levels deeper than this example; be sure to count all of the bags, even if the nesting becomes topologically impractical!
+
+Here's another example:
+
+```example part2=126
+shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.
+```
+
+In this example, a single shiny gold bag must contain 126 other bags.
+
+How many individual bags are required inside your single shiny gold bag?
+
+*/
+
+use crate::error::column;
+use crate::input::parse_lines;
+use crate::{AocError, Result, Solution};
+use std::collections::HashSet;
+use std::{collections::HashMap, string::String};
+
+static INPUT: &str = include_str!("day07-input.txt");
+
+type Rule = (String, Vec<(usize, String)>);
+
+fn parse_line(rule: &str) -> Result<(&str, Vec<(usize, &str)>)> {
+    let error = |msg: String, at: &str| AocError::new(Day07::DAY, msg).at_column(column(rule, at));
+
+    let splited: Vec<&str> = rule.split(" bags contain ").collect();
+    if splited.len() != 2 {
+        return Err(error("expected '<color> bags contain ...'".into(), rule));
+    }
+    let bag_color = splited[0];
+    let can_contain = splited[1]
+        .strip_suffix('.')
+        .ok_or_else(|| error("expected rule to end with '.'".into(), &rule[rule.len()..]))?;
+
+    if can_contain == "no other bags" {
+        Ok((bag_color, vec![]))
+    } else {
+        let res = can_contain
+            .split(", ")
+            .map(|a| {
+                let (count, bag_name): (&str, &str) = a
+                    .trim_end_matches('s')
+                    .trim_end_matches("bag")
+                    .split_once(' ')
+                    .ok_or_else(|| {
+                        error(format!("expected '<count> <color> bags', found '{}'", a), a)
+                    })?;
+                match count.parse::<usize>() {
+                    Ok(count) if count > 0 => Ok((count, bag_name.trim())),
+                    _ => Err(error(format!("invalid bag count '{}'", count), count)),
+                }
+            })
+            .collect::<Result<_>>()?;
+        Ok((bag_color, res))
+    }
+}
+
+/// The number of bags that eventually contain a `bag_type` bag.
+fn count_bags_helper(bag_can_be_in: &HashMap<&str, Vec<(usize, &str)>>, bag_type: &str) -> usize {
+    let mut seen: HashSet<&str> = HashSet::new();
+    let mut stack = vec![bag_type];
+    while let Some(bag) = stack.pop() {
+        for (_, outer) in bag_can_be_in.get(bag).into_iter().flatten() {
+            if seen.insert(outer) {
+                stack.push(outer);
+            }
+        }
+    }
+    seen.len()
+}
+
+enum Mark {
+    /// Its contents are being counted, meeting it again means a cycle.
+    Counting,
+    Counted(usize),
+}
+
+/// The number of bags inside a `bag` bag.
+///
+/// Walks the rules depth first with a stack of its own rather than
+/// recursing, chains of bags can be as long as the input.
+fn count_sub_bags_inside_helper<'a>(
+    bag_contains: &HashMap<&'a str, Vec<(usize, &'a str)>>,
+    bag: &'a str,
+) -> Result<usize> {
+    let cycle = |bag: &str| {
+        AocError::new(
+            Day07::DAY,
+            format!("bag rules contain a cycle through '{}'", bag),
+        )
+    };
+
+    let mut marks: HashMap<&str, Mark> = HashMap::new();
+    // Every bag is visited once to push its contents, then once more to add
+    // them up after they are counted
+    let mut stack = vec![(bag, false)];
+    while let Some((bag, contents_counted)) = stack.pop() {
+        let contents = bag_contains.get(bag).map_or(&[][..], Vec::as_slice);
+        if contents_counted {
+            let total = contents
+                .iter()
+                .try_fold(0_usize, |total, (count, inner)| {
+                    let n = match marks.get(inner) {
+                        Some(Mark::Counted(n)) => *n,
+                        _ => unreachable!("contents are counted first"),
+                    };
+                    n.checked_add(1)?.checked_mul(*count)?.checked_add(total)
+                })
+                .ok_or_else(|| {
+                    let msg = format!("a '{}' bag holds too many bags to count", bag);
+                    AocError::new(Day07::DAY, msg)
+                })?;
+            marks.insert(bag, Mark::Counted(total));
+            continue;
+        }
+
+        match marks.get(bag) {
+            Some(Mark::Counted(_)) => continue,
+            Some(Mark::Counting) => return Err(cycle(bag)),
+            None => {}
+        }
+        marks.insert(bag, Mark::Counting);
+        stack.push((bag, true));
+        for (_, inner) in contents {
+            match marks.get(inner) {
+                Some(Mark::Counted(_)) => {}
+                Some(Mark::Counting) => return Err(cycle(inner)),
+                None => stack.push((inner, false)),
+            }
+        }
+    }
+
+    match marks.get(bag) {
+        Some(Mark::Counted(n)) => Ok(*n),
+        _ => unreachable!("the bag itself is counted last"),
+    }
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    const INPUT: &'static str = INPUT;
+
+    type Input = Vec<Rule>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<Rule>> {
+        parse_lines(input, |line| {
+            let (bag, can_carry) = parse_line(line)?;
+            let can_carry = can_carry
+                .into_iter()
+                .map(|(count, bagname)| (count, bagname.to_string()))
+                .collect();
+            Ok((bag.to_string(), can_carry))
+        })
+    }
+
+    fn part1(&self, rules: &Vec<Rule>) -> Result<usize> {
+        let mut bag_can_be_in: HashMap<&str, Vec<(usize, &str)>> = HashMap::new();
+
+        rules.iter().for_each(|(bag, can_carry)| {
+            for (count, bagname) in can_carry {
+                bag_can_be_in
+                    .entry(bagname)
+                    .or_default()
+                    .push((*count, bag));
+            }
+        });
+
+        Ok(count_bags_helper(&bag_can_be_in, "shiny gold"))
+    }
+
+    fn part2(&self, rules: &Vec<Rule>) -> Result<usize> {
+        let bag_contains: HashMap<&str, Vec<(usize, &str)>> = rules
+            .iter()
+            .map(|(bag, can_carry)| {
+                let can_carry = can_carry.iter().map(|(c, b)| (*c, b.as_str())).collect();
+                (bag.as_str(), can_carry)
+            })
+            .collect();
+        count_sub_bags_inside_helper(&bag_contains, "shiny gold")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEST_INPUT1: &str = include_str!("day07-test-input1.txt");
+    static TEST_INPUT2: &str = include_str!("day07-test-input2.txt");
+
+    fn count_bags(rules: &str) -> usize {
+        Day07.part1(&Day07.parse(rules).unwrap()).unwrap()
+    }
+
+    fn count_bags_inside(rules: &str) -> usize {
+        Day07.part2(&Day07.parse(rules).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(4, count_bags(TEST_INPUT1));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(32, count_bags_inside(TEST_INPUT1));
+        assert_eq!(126, count_bags_inside(TEST_INPUT2));
+    }
+
+    #[test]
+    fn test_bad_input() {
+        let err = Day07
+            .parse(
+                "faded blue bags contain no other bags.\nshiny gold bags contain x dark red bags.",
+            )
+            .unwrap_err();
+        assert_eq!((Some(2), Some(25)), (err.line, err.column));
+
+        let cyclic = Day07
+            .parse(
+                "shiny gold bags contain 1 dark red bag.\ndark red bags contain 2 shiny gold bags.",
+            )
+            .unwrap();
+        assert!(Day07.part2(&cyclic).is_err());
+    }
+
+    #[test]
+    fn test_long_chain() {
+        // Bags 0 to n - 1 hold each other and then a shiny gold bag, which
+        // holds bags n to 2n - 1 the same way
+        let n = 50_000;
+        let rule = |i: usize| match i {
+            _ if i == n - 1 => format!("dark c{} bags contain 1 shiny gold bag.", i),
+            _ if i == 2 * n - 1 => format!("dark c{} bags contain no other bags.", i),
+            _ => format!("dark c{} bags contain 1 dark c{} bag.", i, i + 1),
+        };
+        let mut rules: Vec<String> = (0..2 * n).map(rule).collect();
+        rules.push(format!("shiny gold bags contain 1 dark c{} bag.", n));
+        let rules = Day07.parse(&rules.join("\n")).unwrap();
+
+        assert_eq!(Ok(n), Day07.part1(&rules));
+        assert_eq!(Ok(n), Day07.part2(&rules));
+    }
+
+    #[test]
+    fn test_too_many_bags() {
+        // Doubling at every step, the count leaves the range of a u64
+        let mut rules: Vec<String> = (0..70)
+            .map(|i| format!("dark c{} bags contain 2 dark c{} bags.", i, i + 1))
+            .collect();
+        rules.push("dark c70 bags contain no other bags.".to_string());
+        rules.push("shiny gold bags contain 1 dark c0 bag.".to_string());
+        let rules = Day07.parse(&rules.join("\n")).unwrap();
+
+        let err = Day07.part2(&rules).unwrap_err();
+        assert!(err.to_string().contains("too many bags"), "{}", err);
+    }
+}
@@ -15,6 +15,7 @@ The boot code is represented as a text file with one instruction per line of tex
 
 For example, consider the following program:
 
+```example part1=5 part2=8
 nop +0
 acc +1
 jmp +4
@@ -24,6 +25,7 @@ acc -99
 acc +1
 jmp -4
 acc +6
+```
 
 These instructions are visited in this order:
 
@@ -88,35 +90,62 @@ Fix the program so that it terminates normally by changing exactly one jmp (to n
 
 */
 
-#![feature(str_split_once)]
+use crate::error::column;
+use crate::input::parse_lines;
+use crate::json::Json;
+use crate::solution::{Answer, Details};
+use crate::{trace, AocError, Result, Solution};
+use std::fmt;
 
+#[derive(Debug, Clone)]
 enum Instruction {
     Acc(isize),
     Jmp(isize),
     Nop(isize),
 }
 
-fn parse(s: &str) -> Instruction {
-    let (inst, b) = s.split_once(" ").unwrap();
-    if inst == "acc" {
-        Instruction::Acc(b.parse().unwrap())
-    } else if inst == "jmp" {
-        Instruction::Jmp(b.parse().unwrap())
-    } else {
-        assert_eq!("nop", inst);
-        Instruction::Nop(b.parse().unwrap())
+impl Instruction {
+    fn operation(&self) -> &'static str {
+        match self {
+            Instruction::Acc(_) => "acc",
+            Instruction::Jmp(_) => "jmp",
+            Instruction::Nop(_) => "nop",
+        }
+    }
+
+    fn argument(&self) -> isize {
+        match self {
+            Instruction::Acc(arg) | Instruction::Jmp(arg) | Instruction::Nop(arg) => *arg,
+        }
+    }
+}
+
+fn parse(s: &str) -> Result<Instruction> {
+    let (inst, b) = s.split_once(' ').ok_or_else(|| {
+        AocError::new(Day08::DAY, "expected '<operation> <argument>'").at_column(1)
+    })?;
+    let arg = b.parse().map_err(|e| {
+        AocError::new(Day08::DAY, format!("invalid argument '{}': {}", b, e))
+            .at_column(column(s, b))
+    })?;
+    match inst {
+        "acc" => Ok(Instruction::Acc(arg)),
+        "jmp" => Ok(Instruction::Jmp(arg)),
+        "nop" => Ok(Instruction::Nop(arg)),
+        _ => Err(AocError::new(Day08::DAY, format!("unknown operation '{}'", inst)).at_column(1)),
     }
 }
 
-struct BootCode {
+#[derive(Debug, Clone)]
+pub struct BootCode {
     instructions: Vec<Instruction>,
 }
 
 impl BootCode {
-    fn from_str(instructions: &str) -> Self {
-        Self {
-            instructions: instructions.lines().map(parse).collect(),
-        }
+    fn from_str(instructions: &str) -> Result<Self> {
+        Ok(Self {
+            instructions: parse_lines(instructions, parse)?,
+        })
     }
 
     fn execute_with_loop_detection(&self) -> (bool, isize) {
@@ -128,7 +157,15 @@ impl BootCode {
         while cur < n && !visited[cur] {
             let mut jmp: isize = 1;
             visited[cur] = true;
-            match self.instructions.get(cur).unwrap() {
+            let inst = self.instructions.get(cur).unwrap();
+            trace!(
+                "line {}: {} {:+}, acc {}",
+                cur + 1,
+                inst.operation(),
+                inst.argument(),
+                accum
+            );
+            match inst {
                 Instruction::Acc(i) => accum += i,
                 Instruction::Jmp(i) => jmp = *i,
                 Instruction::Nop(_) => {}
@@ -136,7 +173,7 @@ impl BootCode {
             if jmp > 0 {
                 cur = cur.wrapping_add(jmp as usize);
             } else {
-                cur = cur.wrapping_sub(jmp.abs() as usize);
+                cur = cur.wrapping_sub(jmp.unsigned_abs());
             }
         }
 
@@ -158,40 +195,87 @@ impl BootCode {
         }
     }
 
-    fn find_bad_instruction(&mut self) -> (usize, isize) {
+    fn find_bad_instruction(&mut self) -> Option<(usize, isize)> {
         let n = self.instructions.len();
         for i in 0..n {
             if self.swap_inst(i) {
+                trace!(
+                    "line {} changed to {}",
+                    i + 1,
+                    self.instructions[i].operation()
+                );
                 let (finished, accum) = self.execute_with_loop_detection();
                 if finished {
-                    return (i, accum);
+                    return Some((i, accum));
                 }
 
                 self.swap_inst(i); // Restore
             }
         }
 
-        (0, 0)
+        None
+    }
+}
+
+/// Outcome of repairing the boot code, displayed as its final accumulator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Repair {
+    pub accumulator: isize,
+    /// Index of the instruction that was swapped.
+    pub index: usize,
+    /// The operation before the swap.
+    pub replaced: &'static str,
+}
+
+impl fmt::Display for Repair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.accumulator)
+    }
+}
+
+impl Answer for Repair {
+    fn details(&self) -> Details {
+        vec![
+            ("index", Json::from(self.index)),
+            ("line", Json::from(self.index + 1)),
+            ("replaced", Json::from(self.replaced)),
+        ]
     }
 }
 
 static INPUT: &str = include_str!("day08-input.txt");
 
-fn main() {
-    let mut boot_code = BootCode::from_str(INPUT);
+pub struct Day08;
 
-    // part 1
-    println!(
-        "Accumulator is {} when first loop is reached",
-        boot_code.execute_with_loop_detection().1
-    );
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    const INPUT: &'static str = INPUT;
 
-    let (fix_at, accum) = boot_code.find_bad_instruction();
+    type Input = BootCode;
+    type Part1 = isize;
+    type Part2 = Repair;
+
+    fn parse(&self, input: &str) -> Result<BootCode> {
+        BootCode::from_str(input)
+    }
 
-    println!(
-        "Finished without loop by changing instruction {}, resulting accumulator: {}",
-        fix_at, accum
-    );
+    fn part1(&self, boot_code: &BootCode) -> Result<isize> {
+        Ok(boot_code.execute_with_loop_detection().1)
+    }
+
+    fn part2(&self, boot_code: &BootCode) -> Result<Repair> {
+        let (index, accum) = boot_code.clone().find_bad_instruction().ok_or_else(|| {
+            AocError::new(
+                Self::DAY,
+                "no single jmp/nop change makes the program terminate",
+            )
+        })?;
+        Ok(Repair {
+            accumulator: accum,
+            index,
+            replaced: boot_code.instructions[index].operation(),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -209,25 +293,29 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        {
-            let test_boot_code = BootCode::from_str(TEST_INPUT);
-            let res = test_boot_code.execute_with_loop_detection();
-            assert_eq!(false, res.0);
-            assert_eq!(5, res.1);
-        }
-        {
-            let real_boot_code = BootCode::from_str(INPUT);
-            let res = real_boot_code.execute_with_loop_detection();
-            assert_eq!(false, res.0);
-            assert_eq!(1810, res.1);
-        }
+        let test_boot_code = BootCode::from_str(TEST_INPUT).unwrap();
+        let res = test_boot_code.execute_with_loop_detection();
+        assert!(!res.0);
+        assert_eq!(5, res.1);
     }
 
     #[test]
     fn test_part2() {
-        let mut test_boot_code = BootCode::from_str(TEST_INPUT);
-        let (fix_index, accum) = test_boot_code.find_bad_instruction();
+        let mut test_boot_code = BootCode::from_str(TEST_INPUT).unwrap();
+        let (fix_index, accum) = test_boot_code.find_bad_instruction().unwrap();
         assert_eq!(7, fix_index);
         assert_eq!(8, accum);
     }
+
+    #[test]
+    fn test_bad_input() {
+        let err = BootCode::from_str("nop +0\nacc +1\njmp 4x").unwrap_err();
+        assert_eq!((Some(3), Some(5)), (err.line, err.column));
+
+        let err = BootCode::from_str("nop +0\nmul +1").unwrap_err();
+        assert_eq!((Some(2), Some(1)), (err.line, err.column));
+
+        let looping = BootCode::from_str("jmp +0\njmp -1").unwrap();
+        assert!(Day08.part2(&looping).is_err());
+    }
 }
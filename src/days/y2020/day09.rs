@@ -0,0 +1,259 @@
+/*
+--- Day 9: Encoding Error ---
+
+With your neighbor happily enjoying their video game, you turn your attention to an open data port on the little screen in the seat in front of you.
+
+Though the port is non-standard, you manage to connect it to your computer through the clever use of several paperclips. Upon connection, the port outputs a series of numbers (your puzzle input).
+
+The data appears to be encrypted with the eXchange-Masking Addition System (XMAS) which, conveniently for you, is an old cypher with an important weakness.
+
+XMAS starts by transmitting a preamble of 25 numbers. After that, each number you receive should be the sum of any two of the 25 immediately previous numbers. The two numbers will have different values, and there might be more than one such pair.
+
+For example, suppose your preamble consists of the numbers 1 through 25 in a random order. To be valid, the next number must be the sum of two of those numbers:
+
+    26 would be a valid next number, as it could be 1 plus 25 (or many other pairs, like 2 and 24).
+    49 would be a valid next number, as it is the sum of 24 and 25.
+    100 would not be valid; no two of the previous 25 numbers sum to 100.
+    50 would also not be valid; although 25 appears in the previous 25 numbers, the two numbers in the pair must be different.
+
+Suppose the 26th number is 45, and the first number (no longer an option, as it is more than 25 numbers ago) was 20. Now, for the next number to be valid, there needs to be some pair of numbers among 1-19, 21-25, or 45 that add up to it:
+
+    26 would still be a valid next number, as 1 and 25 are still within the previous 25 numbers.
+    65 would not be valid, as no two of the available numbers sum to it.
+    64 and 66 would both be valid, as they are the result of 19+45 and 21+45 respectively.
+
+Here is a larger example which only considers the previous 5 numbers (and has a preamble of length 5):
+
+```example preamble=5 part1=127 part2=62
+35
+20
+15
+25
+47
+40
+62
+55
+65
+95
+102
+117
+150
+182
+127
+219
+299
+277
+309
+576
+```
+
+In this example, after the 5-number preamble, almost every number is the sum of two of the previous 5 numbers; the only number that does not follow this rule is 127.
+
+The first step of attacking the weakness in the XMAS data is to find the first number in the list (after the preamble) which is not the sum of two of the 25 numbers before it. What is the first number that does not have this property?
+
+*/
+
+/*
+The final step in breaking the XMAS encryption relies on the invalid number you just found: you must find a contiguous set of at least two numbers in your list which sum to the invalid number from step 1.
+
+Again consider the above example:
+
+35
+20
+15
+25
+47
+40
+62
+55
+65
+95
+102
+117
+150
+182
+127
+219
+299
+277
+309
+576
+
+In this list, adding up all of the numbers from 15 through 40 produces the invalid number from step 1, 127. (Of course, the contiguous set of numbers in your actual list might be much longer.)
+
+To find the encryption weakness, add together the smallest and largest number in this contiguous range; in this example, these are 15 and 47, producing 62.
+
+What is the encryption weakness in your XMAS-encrypted list of numbers?
+*/
+
+use crate::input::parse_lines;
+use crate::{AocError, Result, Solution};
+use itertools::Itertools;
+
+static INPUT: &str = include_str!("day09-input.txt");
+
+pub struct Day09 {
+    preamble: usize,
+}
+
+impl Default for Day09 {
+    fn default() -> Self {
+        Self { preamble: 25 }
+    }
+}
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    const INPUT: &'static str = INPUT;
+
+    type Input = Vec<u64>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(&self, input: &str) -> Result<Vec<u64>> {
+        to_numbers_iter(input)
+    }
+
+    fn part1(&self, nums: &Vec<u64>) -> Result<u64> {
+        find_num(nums, self.preamble).ok_or_else(|| {
+            AocError::new(
+                Self::DAY,
+                format!(
+                    "every number is a sum of two of the {} before it",
+                    self.preamble
+                ),
+            )
+        })
+    }
+
+    fn part2(&self, nums: &Vec<u64>) -> Result<u64> {
+        let target = self.part1(nums)?;
+        part_2(nums, target).ok_or_else(|| {
+            AocError::new(Self::DAY, format!("no contiguous range sums to {}", target))
+        })
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "preamble" => {
+                self.preamble = value.parse().ok().filter(|n| *n > 0).ok_or_else(|| {
+                    AocError::new(Self::DAY, format!("invalid preamble length '{}'", value))
+                })?;
+                Ok(())
+            }
+            _ => Err(AocError::new(
+                Self::DAY,
+                format!("unknown parameter '{}'", name),
+            )),
+        }
+    }
+}
+
+fn find_num(numbers: &[u64], premable_length: usize) -> Option<u64> {
+    let res = numbers.windows(premable_length + 1).find(|slice| {
+        let target = *slice.last().unwrap();
+        slice
+            .get(0..premable_length)
+            .unwrap()
+            .iter()
+            .combinations(2)
+            .find(|x| x[0] + x[1] == target)
+            .is_none()
+    });
+
+    res.and_then(|slice| slice.last().copied())
+}
+
+fn to_numbers_iter(input: &str) -> Result<Vec<u64>> {
+    parse_lines(input, |x| {
+        x.parse::<u64>().map_err(|e| {
+            AocError::new(Day09::DAY, format!("invalid number '{}': {}", x, e)).at_column(1)
+        })
+    })
+}
+
+/// Sum of the smallest and largest number in the contiguous range of at
+/// least two numbers that adds up to `target`.
+fn part_2(numbers: &[u64], target: u64) -> Option<u64> {
+    // The range is numbers[i..=j], never shrunk below two numbers so that
+    // `target` itself does not count as a range
+    let mut i = 0;
+    let mut j = 1;
+    let mut sum = numbers.first()? + numbers.get(1)?;
+    while sum != target {
+        if sum > target && j - i > 1 {
+            sum -= numbers[i];
+            i += 1;
+        } else {
+            j += 1;
+            sum += numbers.get(j)?;
+        }
+    }
+
+    let (min, max) = numbers[i..=j].iter().minmax().into_option()?;
+    Some(min + max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::Rng;
+
+    static TEST_INPUT: &str = include_str!("day09-test-input.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            Some(127),
+            find_num(&to_numbers_iter(TEST_INPUT).unwrap(), 5)
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        let nums = to_numbers_iter(TEST_INPUT).unwrap();
+        let num = find_num(&nums, 5).unwrap();
+        assert_eq!(Some(62), part_2(&nums, num));
+    }
+
+    #[test]
+    fn test_part2_without_range() {
+        assert_eq!(None, part_2(&[], 5));
+        assert_eq!(None, part_2(&[5], 5));
+        // Only the target itself adds up to it
+        assert_eq!(None, part_2(&[1, 5, 10], 5));
+        assert_eq!(Some(5), part_2(&[1, 3, 2, 10], 5));
+    }
+
+    /// The answers of every range of at least two numbers adding up to
+    /// `target`.
+    fn naive_part_2(numbers: &[u64], target: u64) -> Vec<u64> {
+        let mut answers = Vec::new();
+        for i in 0..numbers.len() {
+            for j in i + 2..=numbers.len() {
+                let range = &numbers[i..j];
+                if range.iter().sum::<u64>() == target {
+                    answers.push(range.iter().min().unwrap() + range.iter().max().unwrap());
+                }
+            }
+        }
+        answers
+    }
+
+    #[test]
+    fn test_part2_matches_naive_search() {
+        let mut rng = Rng::new(9);
+        for _ in 0..500 {
+            let nums: Vec<u64> = (0..rng.range(0, 15))
+                .map(|_| rng.range(1, 20) as u64)
+                .collect();
+            let target = rng.range(1, 60) as u64;
+
+            let answers = naive_part_2(&nums, target);
+            match part_2(&nums, target) {
+                Some(answer) => assert!(answers.contains(&answer), "{} in {:?}", target, nums),
+                None => assert!(answers.is_empty(), "{} in {:?}", target, nums),
+            }
+        }
+    }
+}
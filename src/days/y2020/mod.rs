@@ -0,0 +1,29 @@
+use crate::Solver;
+
+/// Where the solvers of 2020 and their inputs live.
+pub const DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/days/y2020");
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+
+/// All implemented days, in calendar order.
+pub fn all() -> Vec<Box<dyn Solver>> {
+    vec![
+        Box::new(day01::Day01),
+        Box::new(day02::Day02),
+        Box::new(day03::Day03),
+        Box::new(day04::Day04),
+        Box::new(day05::Day05),
+        Box::new(day06::Day06),
+        Box::new(day07::Day07),
+        Box::new(day08::Day08),
+        Box::new(day09::Day09::default()),
+    ]
+}
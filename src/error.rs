@@ -0,0 +1,103 @@
+use std::error::Error;
+use std::fmt;
+
+pub type Result<T> = std::result::Result<T, AocError>;
+
+/// Error reported by a day's parser or solver.
+///
+/// Line and column are 1-based and refer to the puzzle input, they are left
+/// out when the error is not tied to a specific place in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AocError {
+    pub day: u8,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl AocError {
+    pub fn new<S: Into<String>>(day: u8, message: S) -> Self {
+        Self {
+            day,
+            line: None,
+            column: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn at(self, line: usize, column: usize) -> Self {
+        Self {
+            line: Some(line),
+            column: Some(column),
+            ..self
+        }
+    }
+
+    /// Sets the line, keeping any column already set.
+    pub fn on_line(self, line: usize) -> Self {
+        Self {
+            line: Some(line),
+            ..self
+        }
+    }
+
+    pub fn at_column(self, column: usize) -> Self {
+        Self {
+            column: Some(column),
+            ..self
+        }
+    }
+
+    /// Attributes the error to `day`, for errors of the helpers shared by
+    /// the days, which leave the day at 0.
+    pub fn for_day(self, day: u8) -> Self {
+        Self { day, ..self }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "day {}", self.day)?;
+        if let Some(line) = self.line {
+            write!(f, ", line {}", line)?;
+        }
+        if let Some(column) = self.column {
+            write!(f, ", column {}", column)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl Error for AocError {}
+
+/// 1-based column of `part` within `line`, `part` must be a slice of `line`.
+pub fn column(line: &str, part: &str) -> usize {
+    let offset = part.as_ptr() as usize - line.as_ptr() as usize;
+    debug_assert!(offset <= line.len());
+    line[..offset].chars().count() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let err = AocError::new(7, "expected ' bags contain '");
+        assert_eq!("day 7: expected ' bags contain '", err.to_string());
+        assert_eq!(
+            "day 7, line 3, column 12: expected ' bags contain '",
+            err.at(3, 12).to_string()
+        );
+        let err = AocError::new(2, "bad policy").on_line(5);
+        assert_eq!("day 2, line 5: bad policy", err.to_string());
+        assert_eq!("day 3, line 5: bad policy", err.for_day(3).to_string());
+    }
+
+    #[test]
+    fn test_column() {
+        let line = "jmp +4x";
+        assert_eq!(1, column(line, &line[0..3]));
+        assert_eq!(5, column(line, &line[4..]));
+    }
+}
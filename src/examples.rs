@@ -0,0 +1,288 @@
+use crate::answers::Check;
+use crate::error::{AocError, Result};
+use crate::{days, runner, Part};
+use regex::Regex;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// An example input with the answers the puzzle text gives for it.
+///
+/// Examples come from two places next to the solvers:
+///
+/// * The `dayNN-test-input*.txt` files. Their expected answers live in a
+///   sidecar file with the same name but an `.answers` extension, one
+///   `<key> <value>` pair per line, `#` starts a comment:
+///
+///   ```text
+///   # day09-test-input.txt
+///   preamble 5
+///   part1 127
+///   part2 62
+///   ```
+///
+/// * The puzzle text at the top of `dayNN.rs`, where the worked examples are
+///   fenced with their answers as `<key>=<value>` pairs:
+///
+///   ````text
+///   ```example preamble=5 part1=127 part2=62
+///   35
+///   20
+///   ...
+///   ```
+///   ````
+///
+/// `part1` and `part2` are the expected answers, any other key is a
+/// parameter passed to the solver.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub day: u8,
+    /// Where the example comes from, e.g. `day07-test-input1.txt` or
+    /// `day01.rs:25`.
+    pub name: String,
+    pub input: String,
+    pub expected: Vec<(Part, String)>,
+    pub params: Vec<(String, String)>,
+}
+
+/// Expected answers and solver parameters of an example.
+pub type Sidecar = (Vec<(Part, String)>, Vec<(String, String)>);
+
+fn add_entry(sidecar: &mut Sidecar, key: &str, value: &str) {
+    match key {
+        "part1" => sidecar.0.push((Part::One, value.to_string())),
+        "part2" => sidecar.0.push((Part::Two, value.to_string())),
+        _ => sidecar.1.push((key.to_string(), value.to_string())),
+    }
+}
+
+/// Parses the contents of a sidecar file.
+pub fn parse_sidecar(text: &str) -> std::result::Result<Sidecar, String> {
+    let mut sidecar = (Vec::new(), Vec::new());
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once(char::is_whitespace)
+            .ok_or_else(|| format!("line {}: expected '<key> <value>'", i + 1))?;
+        add_entry(&mut sidecar, key, value.trim());
+    }
+    Ok(sidecar)
+}
+
+/// Extracts the fenced examples from the puzzle text in the source of a day.
+///
+/// `file_name` is only used to name the examples.
+pub fn from_statement(
+    day: u8,
+    file_name: &str,
+    source: &str,
+) -> std::result::Result<Vec<Example>, String> {
+    let mut examples = Vec::new();
+    let mut lines = source.lines().enumerate();
+    while let Some((i, line)) = lines.next() {
+        let info = match line.strip_prefix("```example") {
+            Some(info) => info,
+            None => continue,
+        };
+
+        let mut sidecar = (Vec::new(), Vec::new());
+        for pair in info.split_whitespace() {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("{}:{}: expected '<key>=<value>'", file_name, i + 1))?;
+            add_entry(&mut sidecar, key, value);
+        }
+        if sidecar.0.is_empty() {
+            return Err(format!("{}:{}: example has no answers", file_name, i + 1));
+        }
+
+        let mut input = String::new();
+        loop {
+            match lines.next() {
+                Some((_, "```")) => break,
+                Some((_, line)) => {
+                    input.push_str(line);
+                    input.push('\n');
+                }
+                None => return Err(format!("{}:{}: example is not closed", file_name, i + 1)),
+            }
+        }
+
+        examples.push(Example {
+            day,
+            name: format!("{}:{}", file_name, i + 1),
+            input,
+            expected: sidecar.0,
+            params: sidecar.1,
+        });
+    }
+    Ok(examples)
+}
+
+/// Finds every example in `dir`, ordered by file name and then by position
+/// in the file.
+///
+/// An example input without a sidecar file is returned without expected
+/// answers, so that it can be reported instead of silently ignored.
+pub fn discover<P: AsRef<Path>>(dir: P) -> io::Result<Vec<Example>> {
+    let test_input = Regex::new(r"^day(\d+)-test-input.*\.txt$").unwrap();
+    let source = Regex::new(r"^day(\d+)\.rs$").unwrap();
+    let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidData, e);
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<_>>()?;
+    paths.sort();
+
+    let mut examples = Vec::new();
+    for path in paths {
+        let name = match path.file_name().and_then(|name| name.to_str()) {
+            Some(name) => name.to_string(),
+            None => continue,
+        };
+        let day = |re: &Regex| {
+            re.captures(&name)
+                .and_then(|caps| caps[1].parse::<u8>().ok())
+        };
+
+        if let Some(day) = day(&source) {
+            let text = fs::read_to_string(&path)?;
+            examples.extend(from_statement(day, &name, &text).map_err(invalid)?);
+        } else if let Some(day) = day(&test_input) {
+            let sidecar = path.with_extension("answers");
+            let (expected, params) = match fs::read_to_string(&sidecar) {
+                Ok(text) => parse_sidecar(&text)
+                    .map_err(|e| invalid(format!("{}: {}", sidecar.display(), e)))?,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => (Vec::new(), Vec::new()),
+                Err(e) => return Err(e),
+            };
+            examples.push(Example {
+                day,
+                name,
+                input: fs::read_to_string(&path)?,
+                expected,
+                params,
+            });
+        }
+    }
+    Ok(examples)
+}
+
+/// Runs the solver of the example's day in `year` on it and compares its
+/// answers.
+pub fn check(year: u16, example: &Example) -> Vec<Check> {
+    let day = example.day;
+    let parts: Vec<Part> = example.expected.iter().map(|(part, _)| *part).collect();
+    let failed = |e: AocError| vec![Err(e); parts.len()];
+
+    let actual: Vec<Result<String>> = match days::get(year, day) {
+        None => failed(AocError::new(day, "day is not implemented")),
+        Some(mut solver) => {
+            let configured = example
+                .params
+                .iter()
+                .try_for_each(|(name, value)| solver.set_param(name, value));
+            match configured {
+                Ok(()) => runner::run(solver.as_ref(), &example.input, &parts)
+                    .into_iter()
+                    .map(|r| r.answer)
+                    .collect(),
+                Err(e) => failed(e),
+            }
+        }
+    };
+
+    example
+        .expected
+        .iter()
+        .zip(actual)
+        .map(|((part, expected), actual)| Check {
+            day,
+            part: *part,
+            input: example.name.clone(),
+            expected: expected.clone(),
+            actual,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sidecar() {
+        let (expected, params) =
+            parse_sidecar("# comment\npreamble 5\n\npart2  62\npart1 127\n").unwrap();
+        assert_eq!(
+            vec![
+                (Part::Two, "62".to_string()),
+                (Part::One, "127".to_string())
+            ],
+            expected
+        );
+        assert_eq!(vec![("preamble".to_string(), "5".to_string())], params);
+        assert!(parse_sidecar("part1").is_err());
+    }
+
+    #[test]
+    fn test_from_statement() {
+        let source = "/*\nFor example:\n\n```example part1=3 size=2\n1\n\n2\n```\n*/\n";
+        let examples = from_statement(4, "day04.rs", source).unwrap();
+        assert_eq!(1, examples.len());
+        assert_eq!("day04.rs:4", examples[0].name);
+        assert_eq!("1\n\n2\n", examples[0].input);
+        assert_eq!(vec![(Part::One, "3".to_string())], examples[0].expected);
+        assert_eq!(
+            vec![("size".to_string(), "2".to_string())],
+            examples[0].params
+        );
+
+        assert!(from_statement(4, "day04.rs", "```example part1=3\n1\n").is_err());
+        assert!(from_statement(4, "day04.rs", "```example\n1\n```").is_err());
+        assert!(from_statement(4, "day04.rs", "```example part1\n1\n```").is_err());
+    }
+
+    #[test]
+    fn test_examples() {
+        let examples = discover(days::y2020::DIR).unwrap();
+        assert!(examples.iter().any(|e| e.name == "day07-test-input2.txt"));
+        assert!(examples.iter().any(|e| e.name.starts_with("day01.rs:")));
+
+        for example in examples.iter() {
+            // Without one, a typo in the answers would go unnoticed
+            assert!(
+                !example.expected.is_empty(),
+                "{} has no expected answers",
+                example.name
+            );
+            for check in check(2020, example) {
+                assert!(
+                    check.passed(),
+                    "day {} part {} on '{}': expected {}, got {:?}",
+                    check.day,
+                    check.part,
+                    check.input,
+                    check.expected,
+                    check.actual
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_bad_param() {
+        let example = Example {
+            day: 9,
+            name: "day09-test-input.txt".to_string(),
+            input: "1\n2\n3\n".to_string(),
+            expected: vec![(Part::One, "127".to_string())],
+            params: vec![("preamble".to_string(), "x".to_string())],
+        };
+        assert!(check(2020, &example)[0].actual.is_err());
+    }
+}
@@ -0,0 +1,165 @@
+use crate::http::{Client, Request};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// The puzzle website, overridden to test against a stub server.
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Where what is downloaded from the website is kept, one directory per
+/// year. Inputs differ per account, so this is not under version control.
+pub const CACHE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/.cache");
+
+/// Where the downloaded inputs of `year` are kept, as `dayNN.txt`.
+pub fn cache_dir(year: u16) -> PathBuf {
+    Path::new(CACHE_DIR).join(year.to_string()).join("inputs")
+}
+
+/// Sent along with every request, as the website asks automated tools to.
+pub const USER_AGENT: &str = concat!("aoc2020/", env!("CARGO_PKG_VERSION"));
+
+/// The URL of the puzzle input of `day` of `year`.
+pub fn input_url(base_url: &str, year: u16, day: u8) -> String {
+    format!(
+        "{}/{}/day/{}/input",
+        base_url.trim_end_matches('/'),
+        year,
+        day
+    )
+}
+
+/// Whether an input was downloaded or already in the cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Origin {
+    Downloaded,
+    Cached,
+}
+
+/// Downloads the puzzle inputs of a year with a session token, each at most
+/// once.
+pub struct Fetcher<'a> {
+    pub client: &'a dyn Client,
+    pub year: u16,
+    pub base_url: String,
+    pub session: String,
+    pub cache_dir: PathBuf,
+}
+
+impl Fetcher<'_> {
+    fn cache_path(&self, day: u8) -> PathBuf {
+        self.cache_dir.join(format!("day{:02}.txt", day))
+    }
+
+    /// The puzzle input of `day`, from the cache if it was downloaded before.
+    pub fn fetch(&self, day: u8) -> io::Result<(String, Origin)> {
+        let path = self.cache_path(day);
+        match fs::read_to_string(&path) {
+            Ok(input) if !input.is_empty() => return Ok((input, Origin::Cached)),
+            Ok(_) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+
+        let url = input_url(&self.base_url, self.year, day);
+        let request = Request::get(url.as_str())
+            .header("Cookie", format!("session={}", self.session))
+            .header("User-Agent", USER_AGENT);
+        let response = self.client.send(&request)?;
+        if !response.is_success() {
+            let reason = match response.status {
+                404 => "the puzzle is not unlocked yet".to_string(),
+                400 | 401 | 403 => "the session token was refused".to_string(),
+                _ => response
+                    .body
+                    .lines()
+                    .next()
+                    .unwrap_or("")
+                    .trim()
+                    .to_string(),
+            };
+            return Err(io::Error::other(format!(
+                "{} answered {}: {}",
+                url, response.status, reason
+            )));
+        }
+        if response.body.is_empty() {
+            return Err(io::Error::other(format!("{} answered an empty input", url)));
+        }
+
+        fs::create_dir_all(&self.cache_dir)?;
+        fs::write(&path, &response.body)?;
+        Ok((response.body, Origin::Downloaded))
+    }
+
+    /// Fetches the input of `day` into `target`, unless `target` already
+    /// holds a non-empty input. Returns how it was fetched, `None` if
+    /// `target` was left alone.
+    pub fn install<P: AsRef<Path>>(&self, day: u8, target: P) -> io::Result<Option<Origin>> {
+        let target = target.as_ref();
+        if target.metadata().is_ok_and(|meta| meta.len() > 0) {
+            return Ok(None);
+        }
+        let (input, origin) = self.fetch(day)?;
+        OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(target)?
+            .write_all(input.as_bytes())?;
+        Ok(Some(origin))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{stub, System};
+
+    #[test]
+    fn test_fetch() {
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-{}", std::process::id()));
+        let (base_url, requests) = stub::serve(vec![(200, "1\n2\n3\n"), (404, "Not found")]);
+        let fetcher = Fetcher {
+            client: &System,
+            year: 2020,
+            base_url,
+            session: "abc".to_string(),
+            cache_dir: dir.join("cache"),
+        };
+
+        let target = dir.join("day01-input.txt");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&target, "").unwrap();
+        assert_eq!(
+            Some(Origin::Downloaded),
+            fetcher.install(1, &target).unwrap()
+        );
+        assert_eq!("1\n2\n3\n", fs::read_to_string(&target).unwrap());
+        let (head, _) = requests.recv().unwrap();
+        assert!(head.starts_with("GET /2020/day/1/input HTTP/1.1\r\n"));
+        assert!(head.contains("Cookie: session=abc\r\n"));
+
+        // An input that is there is left alone, a missing one comes from
+        // the cache
+        fs::write(&target, "edited").unwrap();
+        assert_eq!(None, fetcher.install(1, &target).unwrap());
+        assert_eq!("edited", fs::read_to_string(&target).unwrap());
+        fs::remove_file(&target).unwrap();
+        assert_eq!(Some(Origin::Cached), fetcher.install(1, &target).unwrap());
+        assert_eq!("1\n2\n3\n", fs::read_to_string(&target).unwrap());
+
+        let e = fetcher.fetch(2).unwrap_err();
+        assert!(e.to_string().contains("not unlocked"), "{}", e);
+        assert!(!fetcher.cache_path(2).exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_input_url() {
+        assert_eq!(
+            "https://adventofcode.com/2020/day/7/input",
+            input_url("https://adventofcode.com/", 2020, 7)
+        );
+    }
+}
@@ -0,0 +1,618 @@
+use crate::examples::Example;
+use crate::Part;
+use std::collections::HashSet;
+
+/// Small deterministic random number generator (SplitMix64), so that a
+/// generated input can be reproduced from its seed.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `lo..=hi`.
+    pub fn range(&mut self, lo: usize, hi: usize) -> usize {
+        assert!(lo <= hi, "empty range {}..={}", lo, hi);
+        lo + (self.next_u64() % (hi - lo + 1) as u64) as usize
+    }
+
+    /// True with a probability of one in `n`.
+    pub fn one_in(&mut self, n: usize) -> bool {
+        self.range(1, n) == 1
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0, items.len() - 1)]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0, i));
+        }
+    }
+}
+
+/// Generates a random input for `day` of 2020 with the answers planted in
+/// it, `None` for days without a generator.
+///
+/// The answers are known from how the input is built rather than by solving
+/// it, so they can be checked with [`examples::check`] like any example.
+///
+/// [`examples::check`]: crate::examples::check
+pub fn generate(day: u8, seed: u64) -> Option<Example> {
+    let generator: fn(&mut Rng) -> Generated = match day {
+        1 => day01,
+        2 => day02,
+        3 => day03,
+        4 => day04,
+        5 => day05,
+        6 => day06,
+        7 => day07,
+        8 => day08,
+        9 => day09,
+        _ => return None,
+    };
+
+    let generated = generator(&mut Rng::new(seed));
+    Some(Example {
+        day,
+        name: format!("generated, seed {}", seed),
+        input: generated.input,
+        expected: vec![(Part::One, generated.part1), (Part::Two, generated.part2)],
+        params: generated.params,
+    })
+}
+
+struct Generated {
+    input: String,
+    part1: String,
+    part2: String,
+    params: Vec<(String, String)>,
+}
+
+impl Generated {
+    fn new<A: ToString, B: ToString>(input: String, part1: A, part2: B) -> Self {
+        Generated {
+            input,
+            part1: part1.to_string(),
+            part2: part2.to_string(),
+            params: Vec::new(),
+        }
+    }
+}
+
+fn lowercase(rng: &mut Rng) -> char {
+    (b'a' + rng.range(0, 25) as u8) as char
+}
+
+/// Expense report with exactly one pair and one triple adding up to 2020.
+///
+/// The planted entries are below 1010 except the partner of the pair, the
+/// other entries are between 1010 and 2020 so that no two of them fit in a
+/// sum, and values that would complete another sum are left out.
+fn day01(rng: &mut Rng) -> Generated {
+    let (t1, t2, t3) = loop {
+        let (t1, t2) = (rng.range(1, 1009), rng.range(1, 1009));
+        if t1 + t2 > 1010 && t1 != t2 {
+            let t3 = 2020 - t1 - t2;
+            if t3 != t1 && t3 != t2 {
+                break (t1, t2, t3);
+            }
+        }
+    };
+    let s = loop {
+        let s = rng.range(1, 1009);
+        if ![t1, t2, t3, t1 + t2, t1 + t3, t2 + t3].contains(&s) {
+            break s;
+        }
+    };
+
+    let small = [s, t1, t2, t3];
+    let mut forbidden: HashSet<usize> = small.iter().map(|x| 2020 - x).collect();
+    for (i, x) in small.iter().enumerate() {
+        for y in small[i + 1..].iter() {
+            forbidden.insert(2020 - x - y);
+        }
+    }
+
+    let mut entries = vec![s, 2020 - s, t1, t2, t3];
+    for _ in 0..rng.range(0, 100) {
+        let filler = rng.range(1011, 2019);
+        if !forbidden.contains(&filler) {
+            entries.push(filler);
+        }
+    }
+    rng.shuffle(&mut entries);
+
+    Generated::new(lines(entries.iter()), s * (2020 - s), t1 * t2 * t3)
+}
+
+/// Password lines built to be valid or invalid under each policy.
+fn day02(rng: &mut Rng) -> Generated {
+    let mut input = String::new();
+    let (mut valid1, mut valid2) = (0, 0);
+    for _ in 0..rng.range(1, 100) {
+        let len = rng.range(2, 20);
+        let a = rng.range(1, len - 1);
+        let b = rng.range(a + 1, len);
+        let c = lowercase(rng);
+
+        // `at_ends` of the two positions hold the letter, `count` in total
+        let at_ends = rng.range(0, 2);
+        let count = rng.range(at_ends, len - 2 + at_ends);
+        let mut holds = vec![false; len];
+        match at_ends {
+            2 => {
+                holds[a - 1] = true;
+                holds[b - 1] = true;
+            }
+            1 => holds[if rng.one_in(2) { a } else { b } - 1] = true,
+            _ => {}
+        }
+        let mut others: Vec<usize> = (1..=len).filter(|p| *p != a && *p != b).collect();
+        rng.shuffle(&mut others);
+        for p in others.iter().take(count - at_ends) {
+            holds[p - 1] = true;
+        }
+
+        let password: String = holds
+            .iter()
+            .map(|holds| loop {
+                let other = lowercase(rng);
+                if *holds {
+                    break c;
+                } else if other != c {
+                    break other;
+                }
+            })
+            .collect();
+        input.push_str(&format!("{}-{} {}: {}\n", a, b, c, password));
+
+        valid1 += (a <= count && count <= b) as usize;
+        valid2 += (at_ends == 1) as usize;
+    }
+    Generated::new(input, valid1, valid2)
+}
+
+const SLOPES: [(usize, usize); 5] = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
+/// Tree map, counting the trees planted on each slope while it is drawn.
+fn day03(rng: &mut Rng) -> Generated {
+    let width = rng.range(1, 31);
+    let height = rng.range(1, 60);
+    let density = rng.range(2, 6);
+
+    let mut hits = [0_u32; 5];
+    let mut input = String::new();
+    for y in 0..height {
+        let row: Vec<bool> = (0..width).map(|_| rng.one_in(density)).collect();
+        for ((right, down), hits) in SLOPES.iter().zip(hits.iter_mut()) {
+            if y % down == 0 && row[(y / down * right) % width] {
+                *hits += 1;
+            }
+        }
+        input.extend(row.iter().map(|tree| if *tree { '#' } else { '.' }));
+        input.push('\n');
+    }
+    Generated::new(input, hits[1], hits.iter().product::<u32>())
+}
+
+const EYE_COLORS: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+
+fn digits(rng: &mut Rng, n: usize) -> String {
+    (0..n)
+        .map(|_| (b'0' + rng.range(0, 9) as u8) as char)
+        .collect()
+}
+
+/// A valid and an invalid value for a required passport field.
+fn passport_field(rng: &mut Rng, key: &str) -> (String, String) {
+    let year = |rng: &mut Rng, lo: usize, hi: usize| {
+        let valid = rng.range(lo, hi).to_string();
+        let invalid = match rng.range(0, 2) {
+            0 => rng.range(lo - 20, lo - 1).to_string(),
+            1 => rng.range(hi + 1, hi + 20).to_string(),
+            _ => format!("0{}", valid),
+        };
+        (valid, invalid)
+    };
+
+    match key {
+        "byr" => year(rng, 1920, 2002),
+        "iyr" => year(rng, 2010, 2020),
+        "eyr" => year(rng, 2020, 2030),
+        "hgt" => {
+            let valid = if rng.one_in(2) {
+                format!("{}cm", rng.range(150, 193))
+            } else {
+                format!("{}in", rng.range(59, 76))
+            };
+            let invalid = match rng.range(0, 2) {
+                0 => format!("{}cm", rng.range(59, 76)),
+                1 => format!("{}in", rng.range(150, 193)),
+                _ => rng.range(59, 193).to_string(),
+            };
+            (valid, invalid)
+        }
+        "hcl" => {
+            let hex = |rng: &mut Rng| -> String {
+                (0..6)
+                    .map(|_| *rng.pick(&b"0123456789abcdef"[..]) as char)
+                    .collect()
+            };
+            let valid = format!("#{}", hex(rng));
+            let invalid = match rng.range(0, 2) {
+                0 => hex(rng),
+                1 => format!("#{}z", &hex(rng)[1..]),
+                _ => format!("#{}", &hex(rng)[1..]),
+            };
+            (valid, invalid)
+        }
+        "ecl" => {
+            let invalid = if rng.one_in(2) { "xry" } else { "amber" };
+            (rng.pick(&EYE_COLORS).to_string(), invalid.to_string())
+        }
+        _ => {
+            let invalid = match rng.range(0, 2) {
+                0 => digits(rng, 8),
+                1 => digits(rng, 10),
+                _ => format!("{}x", digits(rng, 8)),
+            };
+            (digits(rng, 9), invalid)
+        }
+    }
+}
+
+/// Passport batch where every passport is complete and valid, has an invalid
+/// value or is missing a field.
+fn day04(rng: &mut Rng) -> Generated {
+    let required = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+    let (mut complete, mut valid) = (0, 0);
+    let mut passports = Vec::new();
+    for _ in 0..rng.range(1, 30) {
+        let mut fields: Vec<(&str, String)> = required
+            .iter()
+            .map(|key| (*key, passport_field(rng, key).0))
+            .collect();
+        match rng.range(0, 2) {
+            0 => {
+                fields.remove(rng.range(0, fields.len() - 1));
+            }
+            1 => {
+                let i = rng.range(0, fields.len() - 1);
+                fields[i].1 = passport_field(rng, fields[i].0).1;
+                complete += 1;
+            }
+            _ => {
+                complete += 1;
+                valid += 1;
+            }
+        }
+        if rng.one_in(2) {
+            fields.push(("cid", rng.range(100, 350).to_string()));
+        }
+        rng.shuffle(&mut fields);
+
+        let mut passport = String::new();
+        for (i, (key, value)) in fields.iter().enumerate() {
+            if i > 0 {
+                passport.push(if rng.one_in(3) { '\n' } else { ' ' });
+            }
+            passport.push_str(&format!("{}:{}", key, value));
+        }
+        passports.push(passport);
+    }
+    Generated::new(passports.join("\n\n") + "\n", complete, valid)
+}
+
+/// Boarding passes of a block of consecutive seats with one seat missing.
+fn day05(rng: &mut Rng) -> Generated {
+    let lo = rng.range(0, 900);
+    let hi = rng.range(lo + 2, (lo + 200).min(1023));
+    let mine = rng.range(lo + 1, hi - 1);
+
+    let mut ids: Vec<usize> = (lo..=hi).filter(|id| *id != mine).collect();
+    rng.shuffle(&mut ids);
+    let passes = ids.iter().map(|id| {
+        (0..10)
+            .map(|bit| {
+                let set = (id >> (9 - bit)) & 1 == 1;
+                match (bit < 7, set) {
+                    (true, false) => 'F',
+                    (true, true) => 'B',
+                    (false, false) => 'L',
+                    (false, true) => 'R',
+                }
+            })
+            .collect::<String>()
+    });
+    Generated::new(lines(passes), hi, mine)
+}
+
+/// Customs groups with the questions everyone answered picked first and the
+/// ones only some answered added on top.
+fn day06(rng: &mut Rng) -> Generated {
+    let (mut anyone, mut everyone) = (0, 0);
+    let mut groups = Vec::new();
+    let count = rng.range(1, 20);
+    while groups.len() < count {
+        let mut letters: Vec<char> = ('a'..='z').collect();
+        rng.shuffle(&mut letters);
+        let people = rng.range(1, 5);
+        let all = rng.range(0, 5);
+        // A single person answers all of their questions alone
+        let some = if people > 1 { rng.range(0, 5) } else { 0 };
+
+        let mut answers: Vec<Vec<char>> = vec![letters[..all].to_vec(); people];
+        for c in letters[all..all + some].iter() {
+            // Answered by a non-empty subset of the group, but not all
+            let mut who: Vec<usize> = (0..people).collect();
+            rng.shuffle(&mut who);
+            for person in who.iter().take(rng.range(1, people - 1)) {
+                answers[*person].push(*c);
+            }
+        }
+        if answers.iter().any(|a| a.is_empty()) {
+            continue;
+        }
+
+        let group: Vec<String> = answers
+            .iter_mut()
+            .map(|a| {
+                rng.shuffle(a);
+                a.iter().collect()
+            })
+            .collect();
+        groups.push(group.join("\n"));
+        anyone += all + some;
+        everyone += all;
+    }
+    Generated::new(groups.join("\n\n") + "\n", anyone, everyone)
+}
+
+const ADJECTIVES: [&str; 12] = [
+    "light", "dark", "bright", "muted", "shiny", "faded", "dotted", "vibrant", "plaid", "pale",
+    "wavy", "dim",
+];
+const COLORS: [&str; 12] = [
+    "red", "orange", "white", "yellow", "gold", "olive", "plum", "blue", "black", "teal", "tan",
+    "lime",
+];
+
+/// Bag rules where bags only contain bags further down a random order, with
+/// the bags that end up holding shiny gold chosen up front.
+fn day07(rng: &mut Rng) -> Generated {
+    let mut names: Vec<String> = ADJECTIVES
+        .iter()
+        .flat_map(|adj| COLORS.iter().map(move |color| format!("{} {}", adj, color)))
+        .filter(|name| name != "shiny gold")
+        .collect();
+    rng.shuffle(&mut names);
+    let n = rng.range(1, 25);
+    names.truncate(n - 1);
+    // Few bags after it keep the number of bags inside shiny gold small
+    let gold = n - 1 - rng.range(0, 6.min(n - 1));
+    names.insert(gold, "shiny gold".to_string());
+
+    let holders: Vec<bool> = (0..n).map(|i| i < gold && rng.one_in(2)).collect();
+    let mut inside = vec![0_usize; n];
+    let mut rules = Vec::new();
+    for i in (0..n).rev() {
+        // Holders must contain shiny gold or a later holder, other bags
+        // before it must not
+        let required: Vec<usize> = (i + 1..n).filter(|j| *j == gold || holders[*j]).collect();
+        let mut allowed: Vec<usize> = (i + 1..n)
+            .filter(|j| holders[i] || i > gold || !required.contains(j))
+            .collect();
+        rng.shuffle(&mut allowed);
+        allowed.truncate(rng.range(0, 3));
+        if holders[i] {
+            let j = *rng.pick(&required);
+            if !allowed.contains(&j) {
+                allowed.push(j);
+            }
+        }
+
+        let contents: Vec<(usize, usize)> = allowed.iter().map(|j| (rng.range(1, 4), *j)).collect();
+        if i >= gold {
+            inside[i] = contents.iter().map(|(c, j)| c * (1 + inside[*j])).sum();
+        }
+        let contents: Vec<String> = contents
+            .iter()
+            .map(|(count, j)| {
+                let plural = if *count == 1 { "" } else { "s" };
+                format!("{} {} bag{}", count, names[*j], plural)
+            })
+            .collect();
+        let contents = if contents.is_empty() {
+            "no other bags".to_string()
+        } else {
+            contents.join(", ")
+        };
+        rules.push(format!("{} bags contain {}.", names[i], contents));
+    }
+    rng.shuffle(&mut rules);
+
+    let holders = holders.iter().filter(|h| **h).count();
+    Generated::new(lines(rules.iter()), holders, inside[gold])
+}
+
+/// Boot code that runs straight to the end once repaired, skipping over
+/// traps with forward jumps, with one instruction swapped to loop instead.
+///
+/// Every `nop` jumps back into the code already run and every trap jumps
+/// back twice, so that no other swap makes the program terminate.
+fn day08(rng: &mut Rng) -> Generated {
+    let mut code: Vec<(&str, isize)> = Vec::new();
+    // Indices of the instructions run by the repaired program
+    let mut run: Vec<usize> = Vec::new();
+    let mut acc = 0;
+    let mut looping_acc = None;
+
+    let steps = rng.range(1, 12);
+    let corrupt = rng.range(0, steps - 1);
+    for step in 0..steps {
+        for _ in 0..rng.range(0, 5) {
+            run.push(code.len());
+            if rng.one_in(4) {
+                let back = *rng.pick(&run) as isize - code.len() as isize;
+                code.push(("nop", back));
+            } else {
+                let arg = rng.range(0, 100) as isize - 50;
+                code.push(("acc", arg));
+                acc += arg;
+            }
+        }
+
+        let at = code.len();
+        run.push(at);
+        if rng.one_in(2) {
+            let back = *rng.pick(&run) as isize - at as isize;
+            code.push(("nop", back));
+            if step == corrupt {
+                code[at].0 = "jmp";
+                looping_acc = Some(acc);
+            }
+        } else {
+            let trap: Vec<isize> = (0..rng.range(0, 4))
+                .map(|_| rng.range(0, 100) as isize - 50)
+                .collect();
+            code.push(("jmp", trap.len() as isize + 3));
+            code.extend(trap.iter().map(|arg| ("acc", *arg)));
+            for _ in 0..2 {
+                let back = *rng.pick(&run) as isize - code.len() as isize;
+                code.push(("jmp", back));
+            }
+            if step == corrupt {
+                code[at].0 = "nop";
+                looping_acc = Some(acc + trap.iter().sum::<isize>());
+            }
+        }
+    }
+
+    let code = code.iter().map(|(op, arg)| format!("{} {:+}", op, arg));
+    // The corrupted step is always reached
+    Generated::new(lines(code), looping_acc.unwrap(), acc)
+}
+
+/// XMAS stream with one number that is not the sum of two before it, which
+/// is instead the sum of a unique contiguous range.
+fn day09(rng: &mut Rng) -> Generated {
+    loop {
+        let preamble = rng.range(2, 25);
+        let mut nums: Vec<u64> = Vec::new();
+        let is_sum = |window: &[u64], n: u64| {
+            window
+                .iter()
+                .enumerate()
+                .any(|(i, a)| window[i + 1..].iter().any(|b| a + b == n && a != b))
+        };
+        let push_sum = |rng: &mut Rng, nums: &mut Vec<u64>| {
+            let window = &nums[nums.len() - preamble..];
+            let (a, b) = (*rng.pick(window), *rng.pick(window));
+            if a != b && !window.contains(&(a + b)) {
+                nums.push(a + b);
+            }
+        };
+
+        while nums.len() < preamble {
+            let n = rng.range(1, 50) as u64;
+            if !nums.contains(&n) {
+                nums.push(n);
+            }
+        }
+        let before = nums.len() + rng.range(0, 20);
+        while nums.len() < before {
+            push_sum(rng, &mut nums);
+        }
+
+        let start = rng.range(0, nums.len() - 2);
+        let end = rng.range(start + 1, nums.len() - 1);
+        let range = &nums[start..=end];
+        let target: u64 = range.iter().sum();
+        let weakness = range.iter().min().unwrap() + range.iter().max().unwrap();
+        let window = &nums[nums.len() - preamble..];
+        if window.contains(&target) || is_sum(window, target) {
+            continue;
+        }
+        nums.push(target);
+
+        let after = nums.len() + rng.range(0, 10);
+        while nums.len() < after {
+            push_sum(rng, &mut nums);
+        }
+
+        let ranges = (0..nums.len())
+            .flat_map(|i| (i + 2..=nums.len()).map(move |j| (i, j)))
+            .filter(|(i, j)| nums[*i..*j].iter().sum::<u64>() == target)
+            .count();
+        if ranges == 1 {
+            let mut generated = Generated::new(lines(nums.iter()), target, weakness);
+            generated.params = vec![("preamble".to_string(), preamble.to_string())];
+            return generated;
+        }
+    }
+}
+
+fn lines<T: ToString>(items: impl Iterator<Item = T>) -> String {
+    items.map(|item| item.to_string() + "\n").collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples;
+
+    #[test]
+    fn test_rng() {
+        let mut rng = Rng::new(7);
+        let numbers: Vec<u64> = (0..3).map(|_| rng.next_u64()).collect();
+        let mut again = Rng::new(7);
+        assert_eq!(
+            numbers,
+            (0..3).map(|_| again.next_u64()).collect::<Vec<_>>()
+        );
+
+        assert!((0..100).all(|_| (3..=5).contains(&rng.range(3, 5))));
+        assert_eq!(4, rng.range(4, 4));
+
+        let mut items: Vec<usize> = (0..10).collect();
+        rng.shuffle(&mut items);
+        items.sort_unstable();
+        assert_eq!((0..10).collect::<Vec<_>>(), items);
+    }
+
+    #[test]
+    fn test_generated() {
+        assert_eq!(None, generate(26, 0));
+        assert_eq!(generate(9, 3), generate(9, 3));
+
+        for day in 1..=9 {
+            for seed in 0..100 {
+                let example = generate(day, seed).unwrap();
+                for check in examples::check(2020, &example) {
+                    assert!(
+                        check.passed(),
+                        "day {} part {} with seed {}: expected {}, got {:?}\n{}",
+                        day,
+                        check.part,
+                        seed,
+                        check.expected,
+                        check.actual,
+                        example.input
+                    );
+                }
+            }
+        }
+    }
+}
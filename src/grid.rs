@@ -0,0 +1,295 @@
+use crate::error::{AocError, Result};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// Position in a grid as `(x, y)`, the column and row counted from the top
+/// left corner.
+pub type Pos = (usize, usize);
+
+const NEIGHBOURS: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const NEIGHBOURS_DIAGONAL: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// Rectangular map of cells stored row by row, as used by the char-map
+/// puzzles.
+///
+/// Lookups take signed coordinates so that stepping off the map can be
+/// expressed directly, the variants differ in what happens there: [`get`]
+/// returns `None`, [`get_clamped`] sticks to the edge, [`get_wrapped`] wraps
+/// around horizontally and [`get_toroidal`] wraps around in both directions.
+///
+/// [`get`]: Grid::get
+/// [`get_clamped`]: Grid::get_clamped
+/// [`get_wrapped`]: Grid::get_wrapped
+/// [`get_toroidal`]: Grid::get_toroidal
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A `width` by `height` grid with every cell set to `value`, which
+    /// cannot be empty.
+    ///
+    /// Like those of [`Grid::parse`], the error is not tied to a day, see
+    /// [`AocError::for_day`].
+    pub fn new(width: usize, height: usize, value: T) -> Result<Self>
+    where
+        T: Clone,
+    {
+        if width == 0 || height == 0 {
+            let msg = format!("{} by {} map is empty", width, height);
+            return Err(AocError::new(0, msg));
+        }
+        Ok(Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        })
+    }
+
+    /// Parses one row per line, converting every char with `f`.
+    ///
+    /// Errors returned by `f` are tagged with the line and column of the char,
+    /// rows of different widths are rejected. Errors are reported for day 0,
+    /// the caller knows which day it is parsing, see [`AocError::for_day`].
+    pub fn parse<F>(input: &str, mut f: F) -> Result<Self>
+    where
+        F: FnMut(char) -> Result<T>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for (i, line) in input.lines().enumerate() {
+            let len = line.chars().count();
+            match width {
+                _ if len == 0 => return Err(AocError::new(0, "empty map row").on_line(i + 1)),
+                Some(w) if w != len => {
+                    let msg = format!("row is {} wide, expected {}", len, w);
+                    return Err(AocError::new(0, msg).on_line(i + 1));
+                }
+                _ => width = Some(len),
+            }
+            for (j, c) in line.chars().enumerate() {
+                cells.push(f(c).map_err(|e| e.at(i + 1, j + 1))?);
+            }
+            height += 1;
+        }
+
+        let width = width.ok_or_else(|| AocError::new(0, "empty map"))?;
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn offset(&self, x: isize, y: isize) -> Option<usize> {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return None;
+        }
+        Some(y as usize * self.width + x as usize)
+    }
+
+    pub fn get(&self, x: isize, y: isize) -> Option<&T> {
+        self.offset(x, y).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, x: isize, y: isize) -> Option<&mut T> {
+        self.offset(x, y).map(move |i| &mut self.cells[i])
+    }
+
+    /// The cell at the closest position on the map.
+    pub fn get_clamped(&self, x: isize, y: isize) -> &T {
+        let x = x.clamp(0, self.width as isize - 1);
+        let y = y.clamp(0, self.height as isize - 1);
+        &self[(x as usize, y as usize)]
+    }
+
+    /// Treats the map as repeating to the left and right, `None` above or
+    /// below it.
+    pub fn get_wrapped(&self, x: isize, y: isize) -> Option<&T> {
+        self.get(x.rem_euclid(self.width as isize), y)
+    }
+
+    /// Treats the map as repeating in every direction.
+    pub fn get_toroidal(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize);
+        let y = y.rem_euclid(self.height as isize);
+        &self[(x as usize, y as usize)]
+    }
+
+    /// All cells with their position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i % width, i / width), cell))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    /// The up to four cells above, left, right and below `pos`.
+    pub fn neighbours(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        self.neighbours_by(pos, &NEIGHBOURS)
+    }
+
+    /// The up to eight cells around `pos`, including the diagonals.
+    pub fn neighbours_diagonal(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        self.neighbours_by(pos, &NEIGHBOURS_DIAGONAL)
+    }
+
+    fn neighbours_by<'a>(
+        &'a self,
+        (x, y): Pos,
+        deltas: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (Pos, &'a T)> {
+        deltas.iter().filter_map(move |(dx, dy)| {
+            let (nx, ny) = (x as isize + dx, y as isize + dy);
+            self.get(nx, ny)
+                .map(|cell| ((nx as usize, ny as usize), cell))
+        })
+    }
+
+    /// Renders the map with one line per row, using `f` for every cell.
+    pub fn render<F>(&self, mut f: F) -> String
+    where
+        F: FnMut(&T) -> char,
+    {
+        let mut text = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            text.extend(row.iter().map(&mut f));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): Pos) -> &T {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is off the map",
+            x,
+            y
+        );
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, (x, y): Pos) -> &mut T {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is off the map",
+            x,
+            y
+        );
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+impl fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(|c| *c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Grid<char> {
+        Grid::parse("ab\r\ncd\nef\n", Ok).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_render() {
+        let grid = sample();
+        assert_eq!((2, 3), (grid.width(), grid.height()));
+        assert_eq!('d', grid[(1, 1)]);
+        assert_eq!("ab\ncd\nef\n", grid.to_string());
+        assert_eq!(
+            vec![&['a', 'b'][..], &['c', 'd'], &['e', 'f']],
+            grid.rows().collect::<Vec<_>>()
+        );
+
+        let err = Grid::parse("ab\nabc", Ok).unwrap_err();
+        assert_eq!(Some(2), err.line);
+        assert!(Grid::parse("", Ok).is_err());
+        assert!(Grid::parse("ab\n\nab", Ok).is_err());
+
+        let err = Grid::parse("..\n.x", |c| match c {
+            '.' => Ok(false),
+            _ => Err(AocError::new(0, "expected '.'")),
+        })
+        .unwrap_err();
+        assert_eq!((Some(2), Some(2)), (err.line, err.column));
+    }
+
+    #[test]
+    fn test_new() {
+        let grid = Grid::new(3, 2, '.').unwrap();
+        assert_eq!("...\n...\n", grid.to_string());
+        assert!(Grid::new(0, 2, '.').is_err());
+        assert!(Grid::new(3, 0, '.').is_err());
+    }
+
+    #[test]
+    fn test_lookups() {
+        let mut grid = sample();
+        assert_eq!(Some(&'a'), grid.get(0, 0));
+        assert_eq!(None, grid.get(-1, 0));
+        assert_eq!(None, grid.get(2, 0));
+        assert_eq!(&'a', grid.get_clamped(-5, -1));
+        assert_eq!(&'f', grid.get_clamped(9, 9));
+        assert_eq!(Some(&'c'), grid.get_wrapped(4, 1));
+        assert_eq!(Some(&'d'), grid.get_wrapped(-1, 1));
+        assert_eq!(None, grid.get_wrapped(0, 3));
+        assert_eq!(&'b', grid.get_toroidal(-1, 3));
+
+        *grid.get_mut(1, 2).unwrap() = 'x';
+        grid[(0, 2)] = 'y';
+        assert_eq!("ab\ncd\nyx\n", grid.to_string());
+    }
+
+    #[test]
+    fn test_neighbours() {
+        fn cells<'a>(it: impl Iterator<Item = (Pos, &'a char)>) -> String {
+            it.map(|(_, c)| *c).collect()
+        }
+
+        let grid = sample();
+        assert_eq!("bc", cells(grid.neighbours((0, 0))));
+        assert_eq!("bcf", cells(grid.neighbours((1, 1))));
+        assert_eq!("abcef", cells(grid.neighbours_diagonal((1, 1))));
+        assert_eq!(
+            vec![((0, 1), &'c'), ((1, 2), &'f')],
+            grid.neighbours((0, 2)).collect::<Vec<_>>()
+        );
+    }
+}
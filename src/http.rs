@@ -0,0 +1,319 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// How long connecting, and then sending or receiving anything, may take.
+pub const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A request to the puzzle website.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: &'static str,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+impl Request {
+    pub fn get<S: Into<String>>(url: S) -> Self {
+        Request {
+            method: "GET",
+            url: url.into(),
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    pub fn post<S: Into<String>>(url: S, body: String) -> Self {
+        Request {
+            method: "POST",
+            body: Some(body),
+            ..Request::get(url)
+        }
+    }
+
+    pub fn header<K: Into<String>, V: Into<String>>(mut self, name: K, value: V) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// Sends requests, so that the commands talking to the website can be tested
+/// against something else.
+pub trait Client {
+    fn send(&self, request: &Request) -> io::Result<Response>;
+}
+
+/// The client used by the commands: plain `http://` URLs, such as a local
+/// stub server, are handled directly, `https://` ones by the `curl` command
+/// since the standard library has no TLS.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct System;
+
+impl Client for System {
+    fn send(&self, request: &Request) -> io::Result<Response> {
+        if request.url.starts_with("http://") {
+            send_plain(request, TIMEOUT)
+        } else {
+            send_curl(request)
+        }
+    }
+}
+
+fn invalid<S: Into<String>>(msg: S) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+/// Connects to the first address `addr` resolves to that answers within
+/// `timeout`.
+fn connect(addr: &str, timeout: Duration) -> io::Result<TcpStream> {
+    let mut last = invalid(format!("'{}' resolves to no address", addr));
+    for addr in addr.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&addr, timeout) {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last = e,
+        }
+    }
+    Err(last)
+}
+
+/// Sends an HTTP/1.1 request over a fresh connection, giving up when the
+/// server is silent for longer than `timeout`.
+fn send_plain(request: &Request, timeout: Duration) -> io::Result<Response> {
+    let rest = request.url.trim_start_matches("http://");
+    let (host, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/"),
+    };
+    let addr = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{}:80", host)
+    };
+
+    let mut stream = connect(&addr, timeout)?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+    let mut head = format!(
+        "{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n",
+        request.method, path, host
+    );
+    for (name, value) in request.headers.iter() {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    let body = request.body.as_deref().unwrap_or("");
+    if request.body.is_some() {
+        head.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    }
+    head.push_str("\r\n");
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(body.as_bytes())?;
+    stream.flush()?;
+
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let status = line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|s| s.parse::<u16>().ok())
+        .ok_or_else(|| invalid(format!("malformed status line '{}'", line.trim_end())))?;
+
+    let mut length = None;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse::<usize>().ok();
+            } else if name.eq_ignore_ascii_case("transfer-encoding") {
+                return Err(invalid("chunked responses are not supported"));
+            }
+        }
+    }
+
+    // Without a length the body ends with the connection
+    let mut body = Vec::new();
+    match length {
+        Some(n) => {
+            body.resize(n, 0);
+            reader.read_exact(&mut body)?;
+        }
+        None => {
+            reader.read_to_end(&mut body)?;
+        }
+    }
+    let body = String::from_utf8(body).map_err(|_| invalid("response is not UTF-8"))?;
+    Ok(Response { status, body })
+}
+
+/// Quotes a value for a curl config file.
+fn curl_quote(s: &str) -> String {
+    let escaped = s
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t");
+    format!("\"{}\"", escaped)
+}
+
+/// Sends a request with curl, passing everything through its config on
+/// stdin so that the session token does not show up in the process list.
+fn send_curl(request: &Request) -> io::Result<Response> {
+    let mut config = format!(
+        "url = {}\nrequest = {}\nsilent\nshow-error\nwrite-out = \"\\n%{{http_code}}\"\nmax-time = {}\n",
+        curl_quote(&request.url),
+        request.method,
+        TIMEOUT.as_secs()
+    );
+    for (name, value) in request.headers.iter() {
+        config.push_str(&format!(
+            "header = {}\n",
+            curl_quote(&format!("{}: {}", name, value))
+        ));
+    }
+    if let Some(body) = &request.body {
+        config.push_str(&format!("data-binary = {}\n", curl_quote(body)));
+    }
+
+    let mut child = Command::new("curl")
+        .args(["--config", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(config.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(format!("curl failed: {}", stderr.trim())));
+    }
+
+    let stdout = String::from_utf8(output.stdout).map_err(|_| invalid("response is not UTF-8"))?;
+    let (body, status) = stdout
+        .rsplit_once('\n')
+        .ok_or_else(|| invalid("curl did not report a status"))?;
+    let status = status
+        .parse::<u16>()
+        .map_err(|_| invalid(format!("curl reported status '{}'", status)))?;
+    Ok(Response {
+        status,
+        body: body.to_string(),
+    })
+}
+
+/// A local server answering with canned responses, for the tests of the
+/// commands talking to the website.
+#[cfg(test)]
+pub(crate) mod stub {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc::{self, Receiver};
+    use std::thread;
+
+    /// Serves `responses` in order, one per connection. Returns the base URL
+    /// and the requests received, each as its head and body.
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, Receiver<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let stream = listener.accept().unwrap().0;
+                let mut reader = BufReader::new(stream);
+
+                let mut head = String::new();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim_end().is_empty() {
+                        break;
+                    }
+                    if let Some(n) = line.strip_prefix("Content-Length: ") {
+                        length = n.trim().parse().unwrap();
+                    }
+                    head.push_str(&line);
+                }
+                let mut request_body = vec![0; length];
+                reader.read_exact(&mut request_body).unwrap();
+
+                let response = format!(
+                    "HTTP/1.1 {} Whatever\r\nContent-Length: {}\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+                let _ = tx.send((head, String::from_utf8(request_body).unwrap()));
+            }
+        });
+        (base_url, rx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_send_plain() {
+        let (base_url, requests) = stub::serve(vec![(200, "hello"), (404, "")]);
+        let request = Request::post(format!("{}/a/b", base_url), "x=1".to_string())
+            .header("Cookie", "session=abc");
+        let response = System.send(&request).unwrap();
+        assert_eq!((200, "hello"), (response.status, response.body.as_str()));
+        assert!(response.is_success());
+
+        let (head, body) = requests.recv().unwrap();
+        assert!(head.starts_with("POST /a/b HTTP/1.1\r\n"));
+        assert!(head.contains("Cookie: session=abc\r\n"));
+        assert_eq!("x=1", body);
+
+        let response = System.send(&Request::get(base_url)).unwrap();
+        assert_eq!(404, response.status);
+        assert!(requests.recv().unwrap().0.starts_with("GET / HTTP/1.1\r\n"));
+    }
+
+    #[test]
+    fn test_send_plain_timeout() {
+        // Connections are queued but never answered
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let e = send_plain(&Request::get(url), Duration::from_millis(50)).unwrap_err();
+        assert!(
+            matches!(
+                e.kind(),
+                io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+            ),
+            "{:?}",
+            e
+        );
+    }
+
+    #[test]
+    fn test_curl_quote() {
+        assert_eq!(r#""a \"b\" \\ c\n""#, curl_quote("a \"b\" \\ c\n"));
+    }
+}
@@ -0,0 +1,356 @@
+use crate::answers;
+use crate::error::Result;
+use crate::Solver;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Directory holding additional named inputs, as `<year>/dayNN/<name>.txt`.
+pub const INPUTS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/inputs");
+
+/// Where a day reads its puzzle input from.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// The `dayNN-input.txt` file compiled into the binary.
+    #[default]
+    Embedded,
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Interprets a command line argument, `-` meaning stdin.
+    pub fn from_arg(arg: &str) -> Self {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(PathBuf::from(arg))
+        }
+    }
+
+    /// The input called `name`, the inverse of [`InputSource::name`] for
+    /// inputs stored under [`INPUTS_DIR`].
+    pub fn named(year: u16, day: u8, name: &str) -> Self {
+        match name {
+            "input" => InputSource::Embedded,
+            _ => InputSource::File(named_path(Path::new(INPUTS_DIR), year, day, name)),
+        }
+    }
+
+    /// Makes sure [`InputSource::named`] finds this input again by its name,
+    /// copying a file from elsewhere into [`INPUTS_DIR`]. Returns the input
+    /// as it is found by its name.
+    ///
+    /// Stdin is refused, it is gone once read.
+    pub fn store(&self, year: u16, day: u8) -> io::Result<InputSource> {
+        self.store_in(Path::new(INPUTS_DIR), year, day)
+    }
+
+    fn store_in(&self, dir: &Path, year: u16, day: u8) -> io::Result<InputSource> {
+        let path = match self {
+            InputSource::Embedded => return Ok(InputSource::Embedded),
+            InputSource::Stdin => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "an input read from stdin cannot be read again, pass it as a file",
+                ))
+            }
+            InputSource::File(path) => path,
+        };
+        let name = self.name();
+        if name == "input" {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "'input' names the embedded input, rename the file",
+            ));
+        }
+        if !answers::is_valid_input_name(&name) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "'{}' cannot be recorded, rename the file without spaces",
+                    name
+                ),
+            ));
+        }
+
+        let target = named_path(dir, year, day, &name);
+        if target.exists() {
+            if fs::canonicalize(&target)? == fs::canonicalize(path)? {
+                return Ok(InputSource::File(target));
+            }
+            if fs::read(&target)? != fs::read(path)? {
+                return Err(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    format!("{} holds another input", target.display()),
+                ));
+            }
+        } else {
+            fs::create_dir_all(target.parent().expect("named inputs are in a directory"))?;
+            fs::copy(path, &target)?;
+        }
+        Ok(InputSource::File(target))
+    }
+
+    pub fn load(&self, solver: &dyn Solver) -> io::Result<String> {
+        match self {
+            InputSource::Embedded => Ok(solver.input().to_string()),
+            InputSource::File(path) => fs::read_to_string(path),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+
+    /// Short name identifying the input, e.g. in reports.
+    pub fn name(&self) -> String {
+        match self {
+            InputSource::Embedded => "input".to_string(),
+            InputSource::File(path) => path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string()),
+            InputSource::Stdin => "stdin".to_string(),
+        }
+    }
+}
+
+/// The directory of the named inputs of `day` of `year`.
+pub fn named_dir(year: u16, day: u8) -> PathBuf {
+    Path::new(INPUTS_DIR)
+        .join(year.to_string())
+        .join(format!("day{:02}", day))
+}
+
+fn named_path(dir: &Path, year: u16, day: u8, name: &str) -> PathBuf {
+    dir.join(year.to_string())
+        .join(format!("day{:02}", day))
+        .join(format!("{}.txt", name))
+}
+
+/// Every `.txt` file in `dir` as an input, ordered by file name.
+pub fn inputs_in<P: AsRef<Path>>(dir: P) -> io::Result<Vec<InputSource>> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "txt") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths.into_iter().map(InputSource::File).collect())
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Embedded => write!(f, "embedded input"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+/// Parses `input` one line at a time, tagging errors with their line number.
+pub fn parse_lines<T, F>(input: &str, mut f: F) -> Result<Vec<T>>
+where
+    F: FnMut(&str) -> Result<T>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| f(line).map_err(|e| e.on_line(i + 1)))
+        .collect()
+}
+
+/// A group of consecutive non-blank lines, as used for records separated by
+/// blank lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record<'a> {
+    /// 1-based line number of the first line in the input.
+    pub first_line: usize,
+    lines: Vec<&'a str>,
+}
+
+impl<'a> Record<'a> {
+    /// 1-based line number of the last line in the input.
+    pub fn last_line(&self) -> usize {
+        self.first_line + self.lines.len() - 1
+    }
+
+    /// The lines of the record, with their line number in the input.
+    pub fn lines(&self) -> impl Iterator<Item = (usize, &'a str)> + '_ {
+        self.lines
+            .iter()
+            .enumerate()
+            .map(move |(i, line)| (self.first_line + i, *line))
+    }
+
+    /// The lines of the record joined by `\n`.
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+}
+
+/// Splits `input` into records separated by one or more blank lines.
+///
+/// Handles `\r\n` line endings, separators containing only whitespace and
+/// any number of leading or trailing blank lines. Trailing whitespace is
+/// removed from every line.
+pub fn records(input: &str) -> Vec<Record<'_>> {
+    let mut records: Vec<Record> = Vec::new();
+    let mut current: Option<Record> = None;
+
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            records.extend(current.take());
+        } else {
+            current
+                .get_or_insert_with(|| Record {
+                    first_line: i + 1,
+                    lines: Vec::new(),
+                })
+                .lines
+                .push(line);
+        }
+    }
+    records.extend(current);
+
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+    use crate::error::AocError;
+
+    #[test]
+    fn test_from_arg() {
+        assert_eq!(InputSource::Stdin, InputSource::from_arg("-"));
+        let file = InputSource::from_arg("inputs/day05/alice.txt");
+        assert_eq!(InputSource::File("inputs/day05/alice.txt".into()), file);
+        assert_eq!("alice", file.name());
+        assert_eq!(InputSource::Embedded, InputSource::named(2020, 5, "input"));
+        assert_eq!(
+            InputSource::File(
+                Path::new(INPUTS_DIR)
+                    .join("2020")
+                    .join("day05")
+                    .join("alice.txt")
+            ),
+            InputSource::named(2020, 5, "alice")
+        );
+        // Only an input passed as `-` is read from stdin
+        assert_ne!(InputSource::Stdin, InputSource::named(2020, 5, "stdin"));
+    }
+
+    #[test]
+    fn test_store() {
+        let dir = std::env::temp_dir().join(format!("aoc-store-{}", std::process::id()));
+        let stored = named_path(&dir, 2020, 1, "alice");
+        let file = dir.join("alice.txt");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&file, "1721\n").unwrap();
+
+        let source = InputSource::File(file.clone());
+        assert_eq!(
+            InputSource::File(stored.clone()),
+            source.store_in(&dir, 2020, 1).unwrap()
+        );
+        assert_eq!("1721\n", fs::read_to_string(&stored).unwrap());
+        // Storing it again, or the stored copy, changes nothing
+        assert!(source.store_in(&dir, 2020, 1).is_ok());
+        assert!(InputSource::File(stored.clone())
+            .store_in(&dir, 2020, 1)
+            .is_ok());
+
+        fs::write(&file, "299\n").unwrap();
+        assert!(source.store_in(&dir, 2020, 1).is_err());
+        assert_eq!("1721\n", fs::read_to_string(&stored).unwrap());
+
+        assert!(InputSource::Stdin.store_in(&dir, 2020, 1).is_err());
+        let input = InputSource::File(dir.join("input.txt"));
+        assert!(input.store_in(&dir, 2020, 1).is_err());
+        let spaced = dir.join("my input.txt");
+        fs::write(&spaced, "1721\n").unwrap();
+        assert!(InputSource::File(spaced).store_in(&dir, 2020, 1).is_err());
+        assert!(!named_path(&dir, 2020, 1, "my input").exists());
+        assert_eq!(
+            InputSource::Embedded,
+            InputSource::Embedded.store_in(&dir, 2020, 1).unwrap()
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_load() {
+        let day01 = days::get(2020, 1).unwrap();
+        let embedded = InputSource::Embedded.load(day01.as_ref()).unwrap();
+        assert_eq!(day01.input(), embedded);
+
+        let path = std::env::temp_dir().join("aoc-input-test-day01.txt");
+        fs::write(&path, "1721\n299\n").unwrap();
+        let loaded = InputSource::File(path.clone()).load(day01.as_ref());
+        fs::remove_file(&path).unwrap();
+        assert_eq!("1721\n299\n", loaded.unwrap());
+
+        let missing = InputSource::from_arg("does/not/exist.txt");
+        assert!(missing.load(day01.as_ref()).is_err());
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let parsed = parse_lines("1\n2\n3", |l| Ok(l.len()));
+        assert_eq!(Ok(vec![1, 1, 1]), parsed);
+
+        let err = parse_lines("1\n2\nx", |l| {
+            l.parse::<u32>()
+                .map_err(|_| AocError::new(1, "not a number").at_column(1))
+        })
+        .unwrap_err();
+        assert_eq!(Some(3), err.line);
+        assert_eq!(Some(1), err.column);
+    }
+
+    #[test]
+    fn test_records() {
+        let input = "\r\na b\r\nc\r\n  \r\n\t\nd \n\n\ne\n";
+        let records = records(input);
+        assert_eq!(3, records.len());
+
+        assert_eq!("a b\nc", records[0].text());
+        assert_eq!((2, 3), (records[0].first_line, records[0].last_line()));
+        assert_eq!(
+            vec![(2, "a b"), (3, "c")],
+            records[0].lines().collect::<Vec<_>>()
+        );
+
+        assert_eq!("d", records[1].text());
+        assert_eq!((6, 6), (records[1].first_line, records[1].last_line()));
+
+        assert_eq!("e", records[2].text());
+        assert_eq!(9, records[2].first_line);
+
+        assert!(super::records("\n \n").is_empty());
+    }
+
+    #[test]
+    fn test_inputs_in() {
+        let dir = std::env::temp_dir().join(format!("aoc-inputs-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["bob.txt", "alice.txt", "bob.answers"].iter() {
+            fs::write(dir.join(name), "1\n").unwrap();
+        }
+
+        let names: Vec<String> = inputs_in(&dir).unwrap().iter().map(|s| s.name()).collect();
+        assert_eq!(vec!["alice", "bob"], names);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,24 @@
+pub mod answers;
+pub mod bench;
+pub mod days;
+pub mod error;
+pub mod examples;
+pub mod fetch;
+pub mod generate;
+pub mod grid;
+pub mod http;
+pub mod input;
+pub mod json;
+pub mod memory;
+pub mod runner;
+pub mod scaffold;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod solution;
+pub mod submit;
+pub mod trace;
+pub mod tui;
+pub mod watch;
+
+pub use error::{AocError, Result};
+pub use solution::{Answer, Part, Solution, Solver};
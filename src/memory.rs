@@ -0,0 +1,160 @@
+use crate::json::Json;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Whether allocations are counted, which takes the `alloc-stats` feature.
+pub const ENABLED: bool = cfg!(feature = "alloc-stats");
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Allocations made by a piece of code, see [`measure`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations, a reallocation counts as one.
+    pub allocations: u64,
+    /// Bytes requested by those allocations.
+    pub bytes: u64,
+    /// Most bytes held at once on top of what was held at the start.
+    pub peak: u64,
+}
+
+impl AllocStats {
+    pub fn to_json(self) -> Json {
+        Json::object(vec![
+            ("allocations", Json::from(self.allocations)),
+            ("bytes", Json::from(self.bytes)),
+            ("peak_bytes", Json::from(self.peak)),
+        ])
+    }
+}
+
+// Counted per thread, so that parts solved concurrently are told apart.
+// Memory freed by another thread than the one that allocated it only shows
+// up there, hence the signed live count.
+thread_local! {
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+    static BYTES: Cell<u64> = const { Cell::new(0) };
+    static LIVE: Cell<i64> = const { Cell::new(0) };
+    static PEAK: Cell<i64> = const { Cell::new(0) };
+}
+
+fn record(allocated: usize, freed: usize) {
+    // The thread locals are gone while a thread shuts down, allocations
+    // made then are not counted
+    let _ = LIVE.try_with(|live| {
+        let now = live.get() + allocated as i64 - freed as i64;
+        live.set(now);
+        PEAK.with(|peak| peak.set(peak.get().max(now)));
+        if allocated > 0 {
+            ALLOCATIONS.with(|n| n.set(n.get() + 1));
+            BYTES.with(|bytes| bytes.set(bytes.get() + allocated as u64));
+        }
+    });
+}
+
+/// The system allocator, counting the allocations of every thread.
+pub struct Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size(), 0);
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(layout.size(), 0);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        record(0, layout.size());
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size, layout.size());
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Runs `f` and counts the allocations it makes on the current thread,
+/// `None` unless the counting allocator is enabled.
+pub fn measure<T, F: FnOnce() -> T>(f: F) -> (T, Option<AllocStats>) {
+    if !ENABLED {
+        return (f(), None);
+    }
+
+    let allocations = ALLOCATIONS.with(Cell::get);
+    let bytes = BYTES.with(Cell::get);
+    let live = LIVE.with(Cell::get);
+    let outer_peak = PEAK.with(|peak| peak.replace(live));
+
+    let res = f();
+
+    let peak = PEAK.with(|peak| peak.replace(outer_peak.max(peak.get())));
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.with(Cell::get) - allocations,
+        bytes: BYTES.with(Cell::get) - bytes,
+        peak: (peak - live).max(0) as u64,
+    };
+    (res, Some(stats))
+}
+
+/// Formats a number of bytes with a binary unit suited to its size, e.g.
+/// `1.5 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    let units = ["KiB", "MiB", "GiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < units.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, units[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure() {
+        let (sum, stats) = measure(|| {
+            let v: Vec<u64> = (0..1000).collect();
+            drop(v);
+            let w: Vec<u8> = vec![1; 100];
+            w.iter().map(|x| *x as u64).sum::<u64>()
+        });
+        assert_eq!(100, sum);
+
+        if ENABLED {
+            let stats = stats.unwrap();
+            assert_eq!(2, stats.allocations);
+            assert_eq!(8100, stats.bytes);
+            assert_eq!(8000, stats.peak);
+
+            // Nested measurements do not disturb the outer one
+            let (_, outer) = measure(|| {
+                let _held: Vec<u8> = Vec::with_capacity(64);
+                let (_, inner) = measure(|| Vec::<u8>::with_capacity(16));
+                assert_eq!(16, inner.unwrap().peak);
+            });
+            assert_eq!(80, outer.unwrap().peak);
+        } else {
+            assert_eq!(None, stats);
+        }
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!("512 B", format_bytes(512));
+        assert_eq!("1.5 KiB", format_bytes(1536));
+        assert_eq!("2.0 MiB", format_bytes(2 * 1024 * 1024));
+        assert_eq!("3072.0 GiB", format_bytes(3 << 40));
+    }
+}
@@ -0,0 +1,578 @@
+use crate::bench::format_duration;
+use crate::error::{AocError, Result};
+use crate::json::Json;
+use crate::memory::{self, AllocStats};
+use crate::solution::{Details, Parsed};
+use crate::trace;
+use crate::{Part, Solver};
+use std::any::Any;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How running a part ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Solved,
+    Failed,
+    Panicked,
+    TimedOut,
+    /// Solved, but slower than the budget of the day.
+    OverBudget,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Status::Solved => "ok",
+            Status::Failed => "FAILED",
+            Status::Panicked => "PANICKED",
+            Status::TimedOut => "TIMEOUT",
+            Status::OverBudget => "OVER BUDGET",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Outcome of running one part of one day.
+pub struct PartResult {
+    pub day: u8,
+    pub part: Part,
+    pub answer: Result<String>,
+    pub status: Status,
+    /// Details of the answer, empty if the part failed.
+    pub details: Details,
+    /// Time spent solving the part, without parsing.
+    pub elapsed: Duration,
+    /// Time the part was allowed to take, see [`Solution::BUDGET`].
+    ///
+    /// [`Solution::BUDGET`]: crate::Solution::BUDGET
+    pub budget: Duration,
+    /// Allocations made solving the part, if they are counted.
+    pub memory: Option<AllocStats>,
+}
+
+impl PartResult {
+    /// True if the part was solved within its budget.
+    pub fn is_ok(&self) -> bool {
+        self.answer.is_ok() && self.status != Status::OverBudget
+    }
+
+    /// How much longer than its budget the part took.
+    pub fn over_budget(&self) -> Option<Duration> {
+        match self.status {
+            Status::OverBudget => Some(self.elapsed.saturating_sub(self.budget)),
+            _ => None,
+        }
+    }
+
+    /// The result as `{day, part, answer, status, elapsed, budget, details}`,
+    /// with the times in nanoseconds.
+    pub fn to_json(&self) -> Json {
+        let mut fields = vec![
+            ("day", Json::from(self.day)),
+            ("part", Json::from(self.part.number())),
+        ];
+        match &self.answer {
+            Ok(answer) => fields.push(("answer", Json::from(answer.as_str()))),
+            Err(e) => {
+                fields.push(("answer", Json::Null));
+                fields.push(("error", Json::from(e.to_string())));
+            }
+        }
+        let status = self.status.to_string().to_lowercase().replace(' ', "_");
+        fields.push(("status", Json::from(status)));
+        fields.push(("elapsed", Json::from(self.elapsed.as_nanos())));
+        fields.push(("budget", Json::from(self.budget.as_nanos())));
+        if let Some(memory) = self.memory {
+            fields.push(("memory", memory.to_json()));
+        }
+        fields.push(("details", Json::object(self.details.clone())));
+        Json::object(fields)
+    }
+}
+
+/// Parses `input` once and solves the requested parts.
+///
+/// A panicking solver is reported as a failed part instead of taking the
+/// whole runner down with it, a part slower than the budget of the day as
+/// [`Status::OverBudget`].
+pub fn run(solver: &dyn Solver, input: &str, parts: &[Part]) -> Vec<PartResult> {
+    let parsed = match catch(solver.day(), None, || solver.parse(input)) {
+        (Ok(parsed), _) => parsed,
+        (Err(e), status) => {
+            let (day, budget) = (solver.day(), solver.budget());
+            return failed(day, budget, parts, e, status, Duration::default());
+        }
+    };
+
+    parts
+        .iter()
+        .map(|&part| solve(solver, &parsed, part, solver.budget()))
+        .collect()
+}
+
+fn solve(solver: &dyn Solver, parsed: &Parsed, part: Part, budget: Duration) -> PartResult {
+    let day = solver.day();
+    let start = Instant::now();
+    let ((res, mut status), memory) = memory::measure(|| {
+        catch(day, Some(part), || {
+            solver.solve_detailed(parsed.as_ref(), part)
+        })
+    });
+    let elapsed = start.elapsed();
+    if status == Status::Solved && elapsed > budget {
+        status = Status::OverBudget;
+    }
+    let (answer, details) = match res {
+        Ok((answer, details)) => (Ok(answer), details),
+        Err(e) => (Err(e), Vec::new()),
+    };
+    PartResult {
+        day,
+        part,
+        answer,
+        status,
+        details,
+        elapsed,
+        budget,
+        memory,
+    }
+}
+
+/// Results of one day from [`run_all`].
+pub struct DayResults {
+    pub day: u8,
+    pub parts: Vec<PartResult>,
+    /// Time spent parsing plus solving every part, regardless of how much of
+    /// it overlapped.
+    pub elapsed: Duration,
+}
+
+/// Results of [`run_all`], in the order the days were given.
+pub struct RunAll {
+    pub days: Vec<DayResults>,
+    /// Wall-clock time of the whole run.
+    pub elapsed: Duration,
+}
+
+type Job<'a> = Box<dyn FnOnce() -> Done + Send + 'a>;
+
+enum Done {
+    Parsed(usize, Duration, (Result<Parsed>, Status)),
+    Solved(usize, usize, PartResult),
+}
+
+/// Runs every solver on its input like [`run`], on a pool of `threads`
+/// workers.
+///
+/// Each day is parsed once, after which its parts are solved concurrently
+/// with each other and with the other days.
+pub fn run_all(days: &[(Box<dyn Solver>, String)], parts: &[Part], threads: usize) -> RunAll {
+    let start = Instant::now();
+    let mut results: Vec<Vec<Option<PartResult>>> = days
+        .iter()
+        .map(|_| parts.iter().map(|_| None).collect())
+        .collect();
+    let mut parse_times = vec![Duration::default(); days.len()];
+
+    let (job_tx, job_rx) = mpsc::channel::<Job>();
+    let job_rx = Mutex::new(job_rx);
+    let (done_tx, done_rx) = mpsc::channel::<Done>();
+
+    thread::scope(|scope| {
+        for _ in 0..threads.max(1) {
+            let (job_rx, done_tx) = (&job_rx, done_tx.clone());
+            scope.spawn(move || loop {
+                // Holding the lock only while waiting, not while working
+                let job = job_rx.lock().unwrap().recv();
+                match job {
+                    Ok(job) => {
+                        let _ = done_tx.send(job());
+                    }
+                    Err(_) => break,
+                }
+            });
+        }
+
+        let mut pending = 0;
+        for (i, (solver, input)) in days.iter().enumerate() {
+            job_tx
+                .send(Box::new(move || {
+                    let start = Instant::now();
+                    let parsed = catch(solver.day(), None, || solver.parse(input));
+                    Done::Parsed(i, start.elapsed(), parsed)
+                }))
+                .unwrap();
+            pending += 1;
+        }
+
+        while pending > 0 {
+            pending -= 1;
+            match done_rx.recv().unwrap() {
+                Done::Parsed(i, elapsed, (Ok(parsed), _)) => {
+                    parse_times[i] = elapsed;
+                    let solver = days[i].0.as_ref();
+                    let parsed = Arc::new(parsed);
+                    for (j, &part) in parts.iter().enumerate() {
+                        let parsed = Arc::clone(&parsed);
+                        job_tx
+                            .send(Box::new(move || {
+                                Done::Solved(i, j, solve(solver, &parsed, part, solver.budget()))
+                            }))
+                            .unwrap();
+                        pending += 1;
+                    }
+                }
+                Done::Parsed(i, elapsed, (Err(e), status)) => {
+                    parse_times[i] = elapsed;
+                    let solver = days[i].0.as_ref();
+                    let (day, budget) = (solver.day(), solver.budget());
+                    let failed = failed(day, budget, parts, e, status, Duration::default());
+                    results[i] = failed.into_iter().map(Some).collect();
+                }
+                Done::Solved(i, j, res) => results[i][j] = Some(res),
+            }
+        }
+        // Closing the queue stops the workers
+        drop(job_tx);
+    });
+
+    let days = days
+        .iter()
+        .zip(results)
+        .zip(parse_times)
+        .map(|((day, results), parse)| {
+            let parts: Vec<PartResult> = results.into_iter().map(Option::unwrap).collect();
+            let elapsed = parse + parts.iter().map(|r| r.elapsed).sum::<Duration>();
+            DayResults {
+                day: day.0.day(),
+                parts,
+                elapsed,
+            }
+        })
+        .collect();
+
+    RunAll {
+        days,
+        elapsed: start.elapsed(),
+    }
+}
+
+/// Like [`run`], but in a thread of its own, giving up on `solver` once
+/// parsing or a part takes longer than `budget`, by default the budget of the
+/// day.
+///
+/// A solver that is given up on keeps running in the background until the
+/// part it is on is done, a thread cannot be stopped from the outside. See
+/// [`Budgeted::wait`] to keep it from competing with what runs next.
+pub fn run_with_budget(
+    solver: Box<dyn Solver>,
+    input: String,
+    parts: &[Part],
+    budget: Option<Duration>,
+) -> Budgeted {
+    let day = solver.day();
+    let budget = budget.unwrap_or_else(|| solver.budget());
+    let (tx, rx) = mpsc::channel();
+    let (running, stopped) = mpsc::channel::<()>();
+    let thread_parts = parts.to_vec();
+    // Sends nothing once parsed, then the result of every part. Sending
+    // fails once the budget ran out and the receiver is gone.
+    thread::spawn(move || {
+        let _running = running;
+        let solver = solver.as_ref();
+        let parsed = match catch(day, None, || solver.parse(&input)) {
+            (Ok(parsed), _) => parsed,
+            (Err(e), status) => {
+                let _ = tx.send(failed(
+                    day,
+                    budget,
+                    &thread_parts,
+                    e,
+                    status,
+                    Duration::default(),
+                ));
+                return;
+            }
+        };
+        let _ = tx.send(Vec::new());
+        for part in thread_parts {
+            if tx.send(vec![solve(solver, &parsed, part, budget)]).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut results = Vec::new();
+    while results.len() < parts.len() {
+        let rest = &parts[results.len()..];
+        match rx.recv_timeout(budget) {
+            Ok(done) => results.extend(done),
+            Err(RecvTimeoutError::Timeout) => {
+                let msg = format!("exceeded the time budget of {}", format_duration(budget));
+                let e = AocError::new(day, msg);
+                results.extend(failed(day, budget, rest, e, Status::TimedOut, budget));
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                let e = AocError::new(day, "solver thread stopped without a result");
+                let status = Status::Panicked;
+                results.extend(failed(day, budget, rest, e, status, Duration::default()));
+            }
+        }
+    }
+    Budgeted {
+        parts: results,
+        stopped,
+    }
+}
+
+/// Outcome of [`run_with_budget`].
+pub struct Budgeted {
+    pub parts: Vec<PartResult>,
+    // Disconnected once the solver thread returned
+    stopped: mpsc::Receiver<()>,
+}
+
+impl Budgeted {
+    /// Waits up to `timeout` for a solver that was given up on to stop,
+    /// returning false if it is still running.
+    pub fn wait(&self, timeout: Duration) -> bool {
+        matches!(
+            self.stopped.recv_timeout(timeout),
+            Err(RecvTimeoutError::Disconnected)
+        )
+    }
+}
+
+fn failed(
+    day: u8,
+    budget: Duration,
+    parts: &[Part],
+    e: AocError,
+    status: Status,
+    elapsed: Duration,
+) -> Vec<PartResult> {
+    parts
+        .iter()
+        .map(|&part| PartResult {
+            day,
+            part,
+            answer: Err(e.clone()),
+            status,
+            details: Vec::new(),
+            elapsed,
+            budget,
+            memory: None,
+        })
+        .collect()
+}
+
+/// Runs `f`, turning a panic into an error. What `f` traces is attributed to
+/// `day` and `part`, see [`trace::scoped`].
+fn catch<T, F: FnOnce() -> Result<T>>(day: u8, part: Option<Part>, f: F) -> (Result<T>, Status) {
+    match panic::catch_unwind(AssertUnwindSafe(|| trace::scoped(day, part, f))) {
+        Ok(Ok(res)) => (Ok(res), Status::Solved),
+        Ok(Err(e)) => (Err(e), Status::Failed),
+        Err(payload) => {
+            let msg = format!("panicked: {}", panic_message(payload.as_ref()));
+            (Err(AocError::new(day, msg)), Status::Panicked)
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "solver panicked".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+    use crate::days::y2020::day08::Day08;
+    use crate::Solution;
+
+    #[test]
+    fn test_run_example() {
+        let day01 = days::get(2020, 1).unwrap();
+        let res = run(
+            day01.as_ref(),
+            "1721\n979\n366\n299\n675\n1456",
+            &Part::BOTH,
+        );
+        assert_eq!(Ok("514579".to_string()), res[0].answer);
+        assert_eq!(Ok("241861950".to_string()), res[1].answer);
+    }
+
+    #[test]
+    fn test_run_bad_input() {
+        let day01 = days::get(2020, 1).unwrap();
+        let res = run(day01.as_ref(), "not a number", &[Part::One]);
+        assert_eq!(1, res.len());
+        let err = res[0].answer.clone().unwrap_err();
+        assert_eq!(Some(1), err.line);
+        assert_eq!(Status::Failed, res[0].status);
+        assert!(res[0]
+            .to_json()
+            .to_string()
+            .contains(r#""answer":null,"error":"#));
+    }
+
+    #[test]
+    fn test_details() {
+        let day08 = days::get(2020, 8).unwrap();
+        let res = run(day08.as_ref(), Day08::INPUT, &Part::BOTH);
+        assert!(res[0].details.is_empty());
+        let json = res[1].to_json().to_string();
+        assert!(json.starts_with(r#"{"day":8,"part":2,"answer":"969","status":"ok","elapsed":"#));
+        assert!(json.ends_with(r#""details":{"index":313,"line":314,"replaced":"jmp"}}"#));
+    }
+
+    #[test]
+    fn test_run_all() {
+        let days: Vec<(Box<dyn Solver>, String)> = days::y2020::all()
+            .into_iter()
+            .map(|solver| {
+                let input = solver.input().to_string();
+                (solver, input)
+            })
+            .collect();
+        let sequential: Vec<Vec<Result<String>>> = days
+            .iter()
+            .map(|(solver, input)| {
+                run(solver.as_ref(), input, &Part::BOTH)
+                    .into_iter()
+                    .map(|r| r.answer)
+                    .collect()
+            })
+            .collect();
+
+        let res = run_all(&days, &Part::BOTH, 4);
+        assert_eq!(days.len(), res.days.len());
+        for ((day, expected), (solver, _)) in res.days.iter().zip(sequential).zip(days.iter()) {
+            assert_eq!(solver.day(), day.day);
+            let answers: Vec<Result<String>> = day.parts.iter().map(|r| r.answer.clone()).collect();
+            assert_eq!(expected, answers);
+            assert_eq!(
+                vec![Part::One, Part::Two],
+                day.parts.iter().map(|r| r.part).collect::<Vec<_>>()
+            );
+        }
+
+        let bad = vec![(days::get(2020, 1).unwrap(), "x".to_string())];
+        let res = run_all(&bad, &[Part::Two], 1);
+        assert_eq!(Status::Failed, res.days[0].parts[0].status);
+    }
+
+    #[test]
+    fn test_run_with_budget() {
+        let res = run_with_budget(
+            days::get(2020, 1).unwrap(),
+            "1721\n299".to_string(),
+            &[Part::One],
+            None,
+        )
+        .parts;
+        assert_eq!(Ok("514579".to_string()), res[0].answer);
+        assert_eq!(Status::Solved, res[0].status);
+        assert_eq!(Duration::from_secs(1), res[0].budget);
+
+        // The solver runs as it was configured
+        let mut day09 = days::get(2020, 9).unwrap();
+        day09.set_param("preamble", "5").unwrap();
+        let input = include_str!("days/y2020/day09-test-input.txt").to_string();
+        let res = run_with_budget(day09, input, &[Part::One], None);
+        assert_eq!(Ok("127".to_string()), res.parts[0].answer);
+        assert!(res.wait(Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_run_with_budget_timeout() {
+        // Part 2 sleeps for 20ms
+        let budget = Duration::from_millis(1);
+        let res = run_with_budget(Box::new(Slow), String::new(), &[Part::Two], Some(budget));
+        assert_eq!(Status::TimedOut, res.parts[0].status);
+        assert!(res.parts[0].answer.is_err());
+        // Both times are the budget given rather than the one of the day
+        assert_eq!(
+            (budget, budget),
+            (res.parts[0].elapsed, res.parts[0].budget)
+        );
+        // The solver given up on still finishes its part
+        assert!(res.wait(Duration::from_secs(1)));
+    }
+
+    struct Slow;
+
+    impl Solution for Slow {
+        const DAY: u8 = 0;
+        const INPUT: &'static str = "";
+        const BUDGET: Duration = Duration::from_millis(1);
+
+        type Input = ();
+        type Part1 = u8;
+        type Part2 = u8;
+
+        fn parse(&self, _input: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn part1(&self, _input: &()) -> Result<u8> {
+            Ok(1)
+        }
+
+        fn part2(&self, _input: &()) -> Result<u8> {
+            thread::sleep(Duration::from_millis(20));
+            Ok(2)
+        }
+    }
+
+    #[test]
+    fn test_over_budget() {
+        let res = run(&Slow, "", &Part::BOTH);
+        assert_eq!(
+            (Status::Solved, None),
+            (res[0].status, res[0].over_budget())
+        );
+        assert!(res[0].is_ok());
+
+        assert_eq!(Status::OverBudget, res[1].status);
+        assert_eq!(Ok("2".to_string()), res[1].answer);
+        assert!(!res[1].is_ok());
+        assert!(res[1].over_budget().unwrap() >= Duration::from_millis(19));
+        assert!(res[1]
+            .to_json()
+            .to_string()
+            .contains(r#""status":"over_budget","#));
+    }
+
+    // Timings of a debug build say little about the budgets, checked by
+    // `cargo test --release` only
+    #[test]
+    #[cfg_attr(debug_assertions, ignore)]
+    fn test_within_budget() {
+        for solver in days::YEARS.iter().flat_map(|year| year.all()) {
+            for res in run(solver.as_ref(), solver.input(), &Part::BOTH) {
+                if let Some(over) = res.over_budget() {
+                    panic!(
+                        "day {} part {} took {}, {} over its budget of {}",
+                        res.day,
+                        res.part,
+                        format_duration(res.elapsed),
+                        format_duration(over),
+                        format_duration(res.budget)
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,343 @@
+use regex::Regex;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Source of a freshly scaffolded day, a solver that parses the input into
+/// lines and fails both parts until they are written.
+pub fn template(day: u8) -> String {
+    format!(
+        r#"/*
+PLACEHOLDER_FOR_INSTRUCTIONS
+*/
+
+/*
+PLACEHOLDER_FOR_INSTRUCTIONS_PART_2
+*/
+
+use crate::input::parse_lines;
+use crate::{{AocError, Result, Solution}};
+
+static INPUT: &str = include_str!("day{day:02}-input.txt");
+
+pub struct Day{day:02};
+
+impl Solution for Day{day:02} {{
+    const DAY: u8 = {day};
+    const INPUT: &'static str = INPUT;
+
+    type Input = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<String>> {{
+        parse_lines(input, |line| Ok(line.to_string()))
+    }}
+
+    fn part1(&self, _lines: &Vec<String>) -> Result<usize> {{
+        Err(AocError::new(Self::DAY, "part 1 is not solved yet"))
+    }}
+
+    fn part2(&self, _lines: &Vec<String>) -> Result<usize> {{
+        Err(AocError::new(Self::DAY, "part 2 is not solved yet"))
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    static TEST_INPUT: &str = include_str!("day{day:02}-test-input.txt");
+
+    #[test]
+    fn test_example() {{
+        let lines = Day{day:02}.parse(TEST_INPUT).unwrap();
+        assert_eq!(TEST_INPUT.lines().count(), lines.len());
+    }}
+}}
+"#,
+        day = day
+    )
+}
+
+/// Adds `day` to the module list and to `all()` of the registry source,
+/// keeping both in calendar order.
+///
+/// In a registry without days yet, like [`year_template`], the module goes
+/// before `all()` and the entry at the start of its list.
+pub fn register(registry: &str, day: u8) -> Result<String, String> {
+    let module = Regex::new(r"^pub mod day(\d+);$").unwrap();
+    let entry = Regex::new(r"^\s*Box::new\(day(\d+)::").unwrap();
+
+    let mut lines: Vec<String> = registry.lines().map(str::to_string).collect();
+    let before_all = lines
+        .iter()
+        .position(|l| l.starts_with("/// All implemented days"));
+    let in_list = lines
+        .iter()
+        .position(|l| l.trim_end().ends_with("vec!["))
+        .map(|i| i + 1);
+    // Without days to go next to, `fallback` tells where and what to insert
+    let mut insert = |re: &Regex, line: String, fallback: Option<(usize, String)>| {
+        let mut at = None;
+        for (i, l) in lines.iter().enumerate() {
+            if let Some(caps) = re.captures(l) {
+                let other: u8 = caps[1].parse().unwrap_or(0);
+                if other == day {
+                    return Err(format!("day {} is already registered", day));
+                }
+                if other < day || at.is_none() {
+                    at = Some(if other < day { i + 1 } else { i });
+                }
+            }
+        }
+        let (at, line) = match (at, fallback) {
+            (Some(at), _) => (at, line),
+            (None, Some(fallback)) => fallback,
+            (None, None) => return Err("registry has no days to insert after".to_string()),
+        };
+        lines.insert(at, line);
+        Ok(())
+    };
+
+    // The entry goes in first, the module shifts the lines after it
+    let entry_line = format!("        Box::new(day{0:02}::Day{0:02}),", day);
+    insert(
+        &entry,
+        entry_line.clone(),
+        in_list.map(|at| (at, entry_line)),
+    )?;
+    let module_line = format!("pub mod day{:02};", day);
+    insert(
+        &module,
+        module_line.clone(),
+        before_all.map(|at| (at, module_line + "\n")),
+    )?;
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Registry source of a freshly scaffolded year, without any days.
+pub fn year_template(year: u16) -> String {
+    format!(
+        r#"use crate::Solver;
+
+/// Where the solvers of {year} and their inputs live.
+pub const DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/days/y{year}");
+
+/// All implemented days, in calendar order.
+pub fn all() -> Vec<Box<dyn Solver>> {{
+    vec![
+    ]
+}}
+"#,
+        year = year
+    )
+}
+
+/// Adds `year` to the module list and to `YEARS` of the registry of years,
+/// keeping both in order.
+pub fn register_year(registry: &str, year: u16) -> Result<String, String> {
+    let module = Regex::new(r"(?m)^pub mod y(\d+);$").unwrap();
+    let entry = Regex::new(r"Year::new\((\d+), y\d+::DIR, y\d+::all\),?").unwrap();
+
+    // Inserts `new` next to the matches of `re`, after the last earlier
+    // year or else before the first one. The entries are matched as text
+    // since rustfmt may put a single one on the line of `YEARS`.
+    let insert = |text: &mut String, re: &Regex, new: &str, sep: &str| -> Result<(), String> {
+        let mut at = None;
+        for caps in re.captures_iter(text) {
+            let m = caps.get(0).unwrap();
+            let other: u16 = caps[1].parse().unwrap_or(0);
+            if other == year {
+                return Err(format!("year {} is already registered", year));
+            }
+            if other < year {
+                // An entry without a trailing comma is the last one
+                let comma = if m.as_str().ends_with(';') || m.as_str().ends_with(',') {
+                    ""
+                } else {
+                    ","
+                };
+                at = Some((m.end(), format!("{}{}{}", comma, sep, new)));
+            } else if at.is_none() {
+                at = Some((m.start(), format!("{}{}", new, sep)));
+            }
+        }
+        let (at, inserted) = at.ok_or("registry has no years to insert after")?;
+        text.insert_str(at, &inserted);
+        Ok(())
+    };
+
+    let mut text = registry.to_string();
+    insert(
+        &mut text,
+        &entry,
+        &format!("Year::new({0}, y{0}::DIR, y{0}::all),", year),
+        "\n    ",
+    )?;
+    insert(&mut text, &module, &format!("pub mod y{};", year), "\n")?;
+    Ok(text)
+}
+
+/// Creates the directory and registry of `year` in `dir` and registers it
+/// in `dir/mod.rs`. Returns the created registry.
+pub fn create_year<P: AsRef<Path>>(dir: P, year: u16) -> io::Result<PathBuf> {
+    let dir = dir.as_ref();
+    let registry_path = dir.join("mod.rs");
+    let registry = register_year(&fs::read_to_string(&registry_path)?, year)
+        .map_err(|e| io::Error::new(io::ErrorKind::AlreadyExists, e))?;
+
+    let year_dir = dir.join(format!("y{}", year));
+    fs::create_dir_all(&year_dir)?;
+    let path = year_dir.join("mod.rs");
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)?
+        .write_all(year_template(year).as_bytes())?;
+    fs::write(registry_path, registry)?;
+    Ok(path)
+}
+
+/// Creates the source, input, example input and example answers files of
+/// `day` in `dir` and registers it in `dir/mod.rs`.
+///
+/// Nothing is written if any of the files already exists or the day is
+/// already registered. Returns the created files.
+pub fn create_day<P: AsRef<Path>>(dir: P, day: u8) -> io::Result<Vec<PathBuf>> {
+    let dir = dir.as_ref();
+    let registry_path = dir.join("mod.rs");
+    let registry = register(&fs::read_to_string(&registry_path)?, day)
+        .map_err(|e| io::Error::new(io::ErrorKind::AlreadyExists, e))?;
+
+    let files = vec![
+        (dir.join(format!("day{:02}.rs", day)), template(day)),
+        (dir.join(format!("day{:02}-input.txt", day)), String::new()),
+        (
+            dir.join(format!("day{:02}-test-input.txt", day)),
+            String::new(),
+        ),
+        (
+            dir.join(format!("day{:02}-test-input.answers", day)),
+            "# part1 <answer>\n# part2 <answer>\n".to_string(),
+        ),
+    ];
+    if let Some((path, _)) = files.iter().find(|(path, _)| path.exists()) {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", path.display()),
+        ));
+    }
+
+    for (path, contents) in files.iter() {
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)?
+            .write_all(contents.as_bytes())?;
+    }
+    fs::write(registry_path, registry)?;
+
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: &str = "pub mod day01;
+pub mod day03;
+
+pub fn all() -> Vec<Box<dyn Solver>> {
+    vec![
+        Box::new(day01::Day01),
+        Box::new(day03::Day03::default()),
+    ]
+}
+";
+
+    #[test]
+    fn test_register() {
+        let registry = register(REGISTRY, 2).unwrap();
+        assert!(registry.starts_with("pub mod day01;\npub mod day02;\npub mod day03;\n"));
+        assert!(registry.contains(
+            "Box::new(day01::Day01),\n        Box::new(day02::Day02),\n        Box::new(day03::"
+        ));
+
+        let registry = register(&registry, 10).unwrap();
+        assert!(registry.contains("pub mod day03;\npub mod day10;\n"));
+        assert!(registry.contains("::default()),\n        Box::new(day10::Day10),\n    ]"));
+
+        assert!(register(REGISTRY, 3).is_err());
+
+        // A new year has no days to go next to
+        let registry = register(&year_template(2021), 4).unwrap();
+        assert!(registry.contains("/src/days/y2021\");\n\npub mod day04;\n\n/// All"));
+        assert!(registry.contains("vec![\n        Box::new(day04::Day04),\n    ]"));
+        let registry = register(&registry, 2).unwrap();
+        assert!(registry.contains("pub mod day02;\npub mod day04;\n"));
+    }
+
+    #[test]
+    fn test_register_year() {
+        let registry = "pub mod y2020;\n\npub const YEARS: &[Year] = &[Year::new(2020, y2020::DIR, y2020::all)];\n";
+        let registry = register_year(registry, 2022).unwrap();
+        assert!(registry.starts_with("pub mod y2020;\npub mod y2022;\n"));
+        assert!(registry.contains(
+            "Year::new(2020, y2020::DIR, y2020::all),\n    Year::new(2022, y2022::DIR, y2022::all),];"
+        ));
+
+        let registry = register_year(&registry, 2015).unwrap();
+        assert!(registry.starts_with("pub mod y2015;\npub mod y2020;\n"));
+        assert!(
+            registry.contains("&[Year::new(2015, y2015::DIR, y2015::all),\n    Year::new(2020,")
+        );
+
+        assert!(register_year(&registry, 2020).is_err());
+    }
+
+    #[test]
+    fn test_create_day() {
+        let dir = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("mod.rs"), REGISTRY).unwrap();
+
+        let files = create_day(&dir, 2).unwrap();
+        assert_eq!(4, files.len());
+        assert_eq!(template(2), fs::read_to_string(&files[0]).unwrap());
+        assert!(fs::read_to_string(dir.join("mod.rs"))
+            .unwrap()
+            .contains("pub mod day02;"));
+
+        // Refuses to overwrite, leaving the registry alone
+        fs::write(dir.join("mod.rs"), REGISTRY).unwrap();
+        let err = create_day(&dir, 2).unwrap_err();
+        assert_eq!(io::ErrorKind::AlreadyExists, err.kind());
+        assert_eq!(REGISTRY, fs::read_to_string(dir.join("mod.rs")).unwrap());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_create_year() {
+        let dir = std::env::temp_dir().join(format!("aoc-scaffold-year-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let years = "pub mod y2020;\n\npub const YEARS: &[Year] = &[\n    Year::new(2020, y2020::DIR, y2020::all),\n];\n";
+        fs::write(dir.join("mod.rs"), years).unwrap();
+
+        let registry = create_year(&dir, 2021).unwrap();
+        assert_eq!(dir.join("y2021").join("mod.rs"), registry);
+        assert!(fs::read_to_string(dir.join("mod.rs"))
+            .unwrap()
+            .contains("pub mod y2021;"));
+        create_day(dir.join("y2021"), 1).unwrap();
+        assert!(fs::read_to_string(&registry)
+            .unwrap()
+            .contains("Box::new(day01::Day01),"));
+
+        assert!(create_year(&dir, 2021).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
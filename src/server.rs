@@ -0,0 +1,399 @@
+use crate::json::Json;
+use crate::runner::{self, Status};
+use crate::{days, Part};
+use regex::Regex;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+/// Largest request body accepted, puzzle inputs are a few tens of KiB.
+pub const MAX_BODY: usize = 1024 * 1024;
+
+/// How long a client may take to send any part of its request.
+pub const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Most inputs solved at once, counting those given up on that still run.
+pub const MAX_SOLVES: usize = 4;
+
+/// Most connections answered at once.
+pub const MAX_CONNECTIONS: usize = 64;
+
+static SOLVES: Slots = Slots::new(MAX_SOLVES);
+static CONNECTIONS: Slots = Slots::new(MAX_CONNECTIONS);
+
+/// A limited number of things going on at once.
+struct Slots {
+    used: AtomicUsize,
+    max: usize,
+}
+
+/// One of the [`Slots`], given back when dropped.
+struct Slot(&'static Slots);
+
+impl Slots {
+    const fn new(max: usize) -> Self {
+        Slots {
+            used: AtomicUsize::new(0),
+            max,
+        }
+    }
+
+    /// A slot, unless they are all used.
+    fn take(&'static self) -> Option<Slot> {
+        self.used
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                (n < self.max).then_some(n + 1)
+            })
+            .ok()
+            .map(|_| Slot(self))
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.used.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// A response, always JSON.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: Json,
+}
+
+impl Response {
+    fn error<S: Into<String>>(status: u16, message: S) -> Self {
+        Response {
+            status,
+            body: Json::object(vec![("error", Json::from(message.into()))]),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            411 => "Length Required",
+            413 => "Payload Too Large",
+            422 => "Unprocessable Entity",
+            503 => "Service Unavailable",
+            _ => "Internal Server Error",
+        }
+    }
+
+    pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        let body = self.body.to_string();
+        write!(
+            w,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            self.reason(),
+            body.len(),
+            body
+        )?;
+        w.flush()
+    }
+}
+
+/// Answers a request, the routes are:
+///
+/// * `GET /<year>/days`: the implemented days.
+/// * `POST /<year>/day/<day>/part/<part>`: solves the part with the body as
+///   the puzzle input. The answer is reported like `aoc run --format json` does,
+///   with the line and column of an error in the input added. Input the
+///   solver rejects is answered with 422, a panicking solver with 500 and a
+///   part slower than the budget of the day with 503, without waiting for it
+///   to finish. So is any input while [`MAX_SOLVES`] are being solved.
+///
+/// Without the year, the routes are those of the latest year.
+pub fn handle(method: &str, path: &str, body: &[u8]) -> Response {
+    handle_with_budget(method, path, body, None)
+}
+
+/// Like [`handle`], solving with `budget` rather than the budget of the day.
+fn handle_with_budget(method: &str, path: &str, body: &[u8], budget: Option<Duration>) -> Response {
+    let days_route = Regex::new(r"^(?:/(\d+))?/days$").unwrap();
+    let solve_route = Regex::new(r"^(?:/(\d+))?/day/(\d+)/part/(\d+)$").unwrap();
+
+    let caps = match days_route
+        .captures(path)
+        .or_else(|| solve_route.captures(path))
+    {
+        Some(caps) => caps,
+        None => return Response::error(404, format!("no route for {}", path)),
+    };
+    let year = match caps.get(1) {
+        None => days::latest(),
+        Some(year) => match year.as_str().parse::<u16>().ok().and_then(days::year) {
+            Some(year) => year,
+            None => return Response::error(404, format!("year {} has no days", year.as_str())),
+        },
+    };
+
+    if caps.len() == 2 {
+        if method != "GET" {
+            return Response::error(405, format!("use GET for {}", path));
+        }
+        let days: Vec<Json> = year.all().iter().map(|s| Json::from(s.day())).collect();
+        return Response {
+            status: 200,
+            body: Json::object(vec![
+                ("year", Json::from(year.year)),
+                ("days", Json::from(days)),
+            ]),
+        };
+    }
+
+    if method != "POST" {
+        return Response::error(405, format!("use POST for {}", path));
+    }
+    let solver = match caps[2].parse::<u8>().ok().and_then(|day| year.get(day)) {
+        Some(solver) => solver,
+        None => return Response::error(404, format!("day {} is not implemented", &caps[2])),
+    };
+    let part = match caps[3].parse::<u8>().ok().and_then(Part::from_number) {
+        Some(part) => part,
+        None => return Response::error(404, format!("there is no part {}", &caps[3])),
+    };
+    let input = match std::str::from_utf8(body) {
+        Ok(input) => input,
+        Err(_) => return Response::error(400, "the puzzle input is not UTF-8"),
+    };
+
+    let slot = match SOLVES.take() {
+        Some(slot) => slot,
+        None => return Response::error(503, "too many inputs are being solved, try again later"),
+    };
+    let mut run = runner::run_with_budget(solver, input.to_string(), &[part], budget);
+    let res = run.parts.remove(0);
+    // A solver given up on holds its slot until it stops
+    if !run.wait(Duration::ZERO) {
+        thread::spawn(move || {
+            run.wait(Duration::MAX);
+            drop(slot);
+        });
+    }
+    let mut json = match res.to_json() {
+        Json::Object(fields) => fields,
+        _ => unreachable!("a part result is an object"),
+    };
+    if let Err(e) = &res.answer {
+        let at = json.iter().position(|(key, _)| key == "error").unwrap() + 1;
+        json.insert(at, ("line".to_string(), Json::from(e.line)));
+        json.insert(at + 1, ("column".to_string(), Json::from(e.column)));
+    }
+    let status = match res.status {
+        Status::Solved => 200,
+        Status::Failed => 422,
+        Status::Panicked => 500,
+        Status::OverBudget | Status::TimedOut => 503,
+    };
+    Response {
+        status,
+        body: Json::Object(json),
+    }
+}
+
+/// Reads one request from `stream` and answers it.
+fn respond<S: Read + Write>(stream: S) -> io::Result<()> {
+    let mut reader = BufReader::new(stream);
+    let response = match read_request(&mut reader)? {
+        Ok((method, path, body)) => handle(&method, &path, &body),
+        Err(response) => response,
+    };
+    response.write_to(reader.get_mut())
+}
+
+type Request = (String, String, Vec<u8>);
+
+/// Reads the method, path and body of a request, or the response to send
+/// when it cannot be handled.
+fn read_request<R: BufRead>(reader: &mut R) -> io::Result<Result<Request, Response>> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let (method, path) = match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+        [method, path, version] if version.starts_with("HTTP/1.") => {
+            (method.to_string(), path.to_string())
+        }
+        _ => return Ok(Err(Response::error(400, "malformed request line"))),
+    };
+
+    let mut length = None;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                match value.trim().parse::<usize>() {
+                    Ok(n) => length = Some(n),
+                    Err(_) => return Ok(Err(Response::error(400, "invalid Content-Length"))),
+                }
+            } else if name.eq_ignore_ascii_case("transfer-encoding") {
+                return Ok(Err(Response::error(
+                    411,
+                    "send the input with a Content-Length",
+                )));
+            }
+        }
+    }
+
+    let length = match length {
+        Some(n) if n > MAX_BODY => {
+            return Ok(Err(Response::error(413, "the puzzle input is too large")))
+        }
+        Some(n) => n,
+        None if method == "POST" => {
+            return Ok(Err(Response::error(
+                411,
+                "send the input with a Content-Length",
+            )))
+        }
+        None => 0,
+    };
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    Ok(Ok((method, path, body)))
+}
+
+/// Answers the connections to `listener`, each on its own thread, until
+/// accepting fails. Beyond [`MAX_CONNECTIONS`], connections are answered
+/// with 503 straight away.
+pub fn serve(listener: TcpListener) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let mut stream: TcpStream = stream?;
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+        match CONNECTIONS.take() {
+            Some(slot) => {
+                thread::spawn(move || {
+                    let _slot = slot;
+                    if let Err(e) = respond(stream) {
+                        eprintln!("Could not answer a request: {}", e);
+                    }
+                });
+            }
+            None => {
+                let busy = Response::error(503, "too many connections, try again later");
+                if let Err(e) = busy.write_to(&mut stream) {
+                    eprintln!("Could not answer a request: {}", e);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Shutdown;
+
+    fn body(res: &Response) -> String {
+        res.body.to_string()
+    }
+
+    #[test]
+    fn test_handle() {
+        let res = handle(
+            "POST",
+            "/2020/day/1/part/1",
+            b"1721\n979\n366\n299\n675\n1456",
+        );
+        assert_eq!(200, res.status);
+        assert!(body(&res).starts_with(r#"{"day":1,"part":1,"answer":"514579","status":"ok","#));
+
+        let res = handle("POST", "/2020/day/1/part/2", b"1721\n97x9");
+        assert_eq!(422, res.status);
+        assert!(
+            body(&res).contains(r#""line":2,"column":1,"#),
+            "{}",
+            body(&res)
+        );
+
+        let res = handle("GET", "/2020/days", b"");
+        assert!(body(&res).starts_with(r#"{"year":2020,"days":[1,2,3,"#));
+        // Without a year the routes are those of the latest year
+        let latest = format!("/{}/days", days::latest().year);
+        assert_eq!(
+            body(&handle("GET", &latest, b"")),
+            body(&handle("GET", "/days", b""))
+        );
+
+        assert_eq!(404, handle("POST", "/2020/day/26/part/1", b"").status);
+        assert_eq!(404, handle("POST", "/2020/day/1/part/3", b"").status);
+        assert_eq!(404, handle("POST", "/2020/day/1", b"").status);
+        assert_eq!(404, handle("POST", "/1999/day/1/part/1", b"").status);
+        assert_eq!(405, handle("POST", "/2020/days", b"").status);
+        assert_eq!(405, handle("GET", "/2020/day/1/part/1", b"").status);
+        assert_eq!(
+            400,
+            handle("POST", "/2020/day/1/part/1", &[0xff, 0xfe]).status
+        );
+
+        // Day 8 tries every swap, which outlasts no budget at all
+        let input = "nop +0\n".repeat(200) + &"jmp -1\n".repeat(200);
+        let res = handle_with_budget(
+            "POST",
+            "/2020/day/8/part/2",
+            input.as_bytes(),
+            Some(Duration::ZERO),
+        );
+        assert_eq!(503, res.status);
+        assert!(
+            body(&res).contains(r#""status":"timeout""#),
+            "{}",
+            body(&res)
+        );
+    }
+
+    #[test]
+    fn test_slots() {
+        static SLOTS: Slots = Slots::new(2);
+        let first = SLOTS.take().unwrap();
+        let second = SLOTS.take().unwrap();
+        assert!(SLOTS.take().is_none());
+        drop(first);
+        assert!(SLOTS.take().is_some());
+        drop(second);
+        assert_eq!(0, SLOTS.used.load(Ordering::SeqCst));
+    }
+
+    fn request(addr: &str, raw: &str) -> String {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(raw.as_bytes()).unwrap();
+        stream.shutdown(Shutdown::Write).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn test_serve() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || serve(listener));
+
+        let input = "abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb";
+        let response = request(
+            &addr,
+            &format!(
+                "POST /2020/day/6/part/1 HTTP/1.1\r\nHost: localhost\r\ncontent-length: {}\r\n\r\n{}",
+                input.len(),
+                input
+            ),
+        );
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+        assert!(response.contains(r#""answer":"11""#), "{}", response);
+
+        let response = request(&addr, "POST /2020/day/6/part/1 HTTP/1.1\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 411 "), "{}", response);
+        let response = request(&addr, "nonsense\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 400 "), "{}", response);
+    }
+}
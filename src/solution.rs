@@ -0,0 +1,156 @@
+use crate::error::{AocError, Result};
+use crate::json::Json;
+use std::any::Any;
+use std::fmt::{self, Display};
+use std::time::Duration;
+
+/// Common interface for the solver of a single day.
+///
+/// The raw puzzle input is parsed once and the parsed form is then handed to
+/// both parts, so that tooling can drive every day the same way.
+pub trait Solution {
+    /// Day of the advent calendar, 1 to 25.
+    const DAY: u8;
+    /// The puzzle input embedded in the binary.
+    const INPUT: &'static str;
+    /// Time each part may take on the real input, a part that takes longer
+    /// fails the run even with the right answer.
+    const BUDGET: Duration = Duration::from_secs(1);
+
+    type Input;
+    type Part1: Answer;
+    type Part2: Answer;
+
+    fn parse(&self, input: &str) -> Result<Self::Input>;
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Part1>;
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Part2>;
+
+    /// Sets a parameter that differs between the examples and the real
+    /// input, such as the length of day 9's preamble.
+    fn set_param(&mut self, name: &str, _value: &str) -> Result<()> {
+        Err(AocError::new(
+            Self::DAY,
+            format!("unknown parameter '{}'", name),
+        ))
+    }
+}
+
+/// Named facts about how an answer was found.
+pub type Details = Vec<(&'static str, Json)>;
+
+/// The answer to one part, displayed as the value to submit.
+///
+/// Answers can carry details such as the instruction that was repaired, which
+/// only show up in the machine readable output.
+pub trait Answer: Display {
+    fn details(&self) -> Details {
+        Vec::new()
+    }
+}
+
+macro_rules! plain_answer {
+    ($($t:ty),*) => {
+        $(impl Answer for $t {})*
+    };
+}
+
+plain_answer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, String);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn from_number(n: u8) -> Option<Part> {
+        match n {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// Object safe version of [`Solution`], so that days with different input
+/// and answer types can be stored side by side in the registry.
+///
+/// The parsed input is passed around as `dyn Any` and the answers are
+/// rendered to strings, along with their details.
+///
+/// Solvers and their parsed inputs are shared between the runner's worker
+/// threads, so days must not keep mutable state outside of `&mut self`.
+pub trait Solver: Send + Sync {
+    fn day(&self) -> u8;
+
+    fn input(&self) -> &'static str;
+
+    fn budget(&self) -> Duration;
+
+    fn parse(&self, input: &str) -> Result<Parsed>;
+
+    fn solve_detailed(&self, parsed: &dyn Any, part: Part) -> Result<(String, Details)>;
+
+    fn solve(&self, parsed: &dyn Any, part: Part) -> Result<String> {
+        self.solve_detailed(parsed, part).map(|(answer, _)| answer)
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<()>;
+}
+
+/// Parsed input of a [`Solver`].
+pub type Parsed = Box<dyn Any + Send + Sync>;
+
+impl<S> Solver for S
+where
+    S: Solution + Send + Sync,
+    S::Input: Send + Sync + 'static,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn input(&self) -> &'static str {
+        S::INPUT
+    }
+
+    fn budget(&self) -> Duration {
+        S::BUDGET
+    }
+
+    fn parse(&self, input: &str) -> Result<Parsed> {
+        let parsed = Solution::parse(self, input)?;
+        Ok(Box::new(parsed))
+    }
+
+    fn solve_detailed(&self, parsed: &dyn Any, part: Part) -> Result<(String, Details)> {
+        let parsed = parsed
+            .downcast_ref::<S::Input>()
+            .ok_or_else(|| AocError::new(S::DAY, "parsed input belongs to another day"))?;
+        match part {
+            Part::One => self.part1(parsed).map(|a| (a.to_string(), a.details())),
+            Part::Two => self.part2(parsed).map(|a| (a.to_string(), a.details())),
+        }
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<()> {
+        Solution::set_param(self, name, value)
+    }
+}
@@ -0,0 +1,449 @@
+use crate::http::{Client, Request};
+use crate::Part;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Every answer submitted for `year`, kept next to its downloaded inputs.
+pub fn history_file(year: u16) -> PathBuf {
+    Path::new(crate::fetch::CACHE_DIR)
+        .join(year.to_string())
+        .join("submissions.txt")
+}
+
+/// How long to wait after a rejected answer before submitting another one
+/// for the same part, the shortest wait the website imposes.
+pub const RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// The URL answers of `day` of `year` are posted to.
+pub fn answer_url(base_url: &str, year: u16, day: u8) -> String {
+    format!(
+        "{}/{}/day/{}/answer",
+        base_url.trim_end_matches('/'),
+        year,
+        day
+    )
+}
+
+/// What the website made of an answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Not judged, another answer was submitted too recently. Holds the
+    /// wait left when the website says.
+    TooSoon(Option<Duration>),
+    /// Not judged, the part is already solved or not unlocked yet.
+    WrongLevel,
+}
+
+impl Verdict {
+    /// Reads the verdict out of the page answering a submission.
+    pub fn parse(page: &str) -> Option<Verdict> {
+        let page = page.to_lowercase();
+        let verdict = if page.contains("that's the right answer") {
+            Verdict::Right
+        } else if page.contains("not the right answer") {
+            if page.contains("too high") {
+                Verdict::TooHigh
+            } else if page.contains("too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            }
+        } else if page.contains("gave an answer too recently") {
+            Verdict::TooSoon(parse_wait(&page))
+        } else if page.contains("solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            return None;
+        };
+        Some(verdict)
+    }
+
+    /// Whether the answer was judged.
+    pub fn is_judged(self) -> bool {
+        !matches!(self, Verdict::TooSoon(_) | Verdict::WrongLevel)
+    }
+
+    /// Whether the submission belongs in the history: judged answers, and
+    /// the waits the website imposes.
+    pub fn is_recorded(self) -> bool {
+        self != Verdict::WrongLevel
+    }
+
+    fn name(self) -> String {
+        match self {
+            Verdict::Right => "right".to_string(),
+            Verdict::Wrong => "wrong".to_string(),
+            Verdict::TooHigh => "too_high".to_string(),
+            Verdict::TooLow => "too_low".to_string(),
+            Verdict::TooSoon(Some(wait)) => format!("too_soon:{}", wait.as_secs()),
+            Verdict::TooSoon(None) => "too_soon".to_string(),
+            Verdict::WrongLevel => "wrong_level".to_string(),
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Verdict> {
+        match name {
+            "right" => Some(Verdict::Right),
+            "wrong" => Some(Verdict::Wrong),
+            "too_high" => Some(Verdict::TooHigh),
+            "too_low" => Some(Verdict::TooLow),
+            "too_soon" => Some(Verdict::TooSoon(None)),
+            _ => {
+                let secs = name.strip_prefix("too_soon:")?.parse().ok()?;
+                Some(Verdict::TooSoon(Some(Duration::from_secs(secs))))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Right => write!(f, "right"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::TooHigh => write!(f, "wrong, too high"),
+            Verdict::TooLow => write!(f, "wrong, too low"),
+            Verdict::TooSoon(Some(wait)) => {
+                write!(f, "not judged, wait {}s", wait.as_secs())
+            }
+            Verdict::TooSoon(None) => write!(f, "not judged, submitted too recently"),
+            Verdict::WrongLevel => write!(f, "not judged, already solved or locked"),
+        }
+    }
+}
+
+/// Reads a wait like "you have 1m 5s left to wait".
+fn parse_wait(page: &str) -> Option<Duration> {
+    let end = page.find(" left to wait")?;
+    let start = page[..end].rfind("you have ")? + "you have ".len();
+    let mut secs = 0;
+    for token in page[start..end].split_whitespace() {
+        let (n, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+        let n = n.parse::<u64>().ok()?;
+        secs += match unit {
+            "h" => n * 3600,
+            "m" => n * 60,
+            "s" => n,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(secs))
+}
+
+/// Encodes `s` for a form body.
+fn form_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Posts `answer` to `part` of `day` of `year` with a session token.
+pub fn submit(
+    client: &dyn Client,
+    base_url: &str,
+    session: &str,
+    year: u16,
+    day: u8,
+    part: Part,
+    answer: &str,
+) -> io::Result<Verdict> {
+    let url = answer_url(base_url, year, day);
+    let body = format!("level={}&answer={}", part.number(), form_encode(answer));
+    let request = Request::post(url.as_str(), body)
+        .header("Cookie", format!("session={}", session))
+        .header("User-Agent", crate::fetch::USER_AGENT)
+        .header("Content-Type", "application/x-www-form-urlencoded");
+    let response = client.send(&request)?;
+    if !response.is_success() {
+        return Err(io::Error::other(format!(
+            "{} answered {}",
+            url, response.status
+        )));
+    }
+    Verdict::parse(&response.body).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} answered with a page without a verdict", url),
+        )
+    })
+}
+
+/// A submission that was judged, or that the website asked to wait after.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub day: u8,
+    pub part: u8,
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+/// The answers submitted so far, used to avoid submitting known wrong
+/// answers again and to pace the submissions.
+///
+/// Stored as a text file with one `<day> <part> <time> <verdict> <answer>`
+/// entry per line, in the order they were submitted. An answer submitted too
+/// soon has the verdict `too_soon:<seconds to wait>`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct History {
+    submissions: Vec<Submission>,
+}
+
+impl History {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut submissions = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.splitn(5, char::is_whitespace).collect();
+            let submission = match fields.as_slice() {
+                [day, part, time, verdict, answer] => (|| {
+                    Some(Submission {
+                        day: day.parse().ok()?,
+                        part: part.parse().ok().filter(|p| *p == 1 || *p == 2)?,
+                        time: time.parse().ok()?,
+                        verdict: Verdict::from_name(verdict)?,
+                        answer: answer.trim().to_string(),
+                    })
+                })(),
+                _ => None,
+            };
+            match submission {
+                Some(submission) => submissions.push(submission),
+                None => {
+                    return Err(format!(
+                        "line {}: expected '<day> <part> <time> <verdict> <answer>'",
+                        i + 1
+                    ))
+                }
+            }
+        }
+        Ok(Self { submissions })
+    }
+
+    /// Loads the history at `path`, a missing file counts as empty.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => {
+                Self::parse(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        if let Some(dir) = path.as_ref().parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_string())
+    }
+
+    pub fn record(&mut self, submission: Submission) {
+        self.submissions.push(submission);
+    }
+
+    /// The submissions for `part` of `day`, oldest first.
+    pub fn of(&self, day: u8, part: Part) -> impl Iterator<Item = &Submission> {
+        self.submissions
+            .iter()
+            .filter(move |s| s.day == day && s.part == part.number())
+    }
+
+    /// Why `answer` should not be submitted at `now`, if there is a reason:
+    /// the part is solved, the answer was already rejected or is out of the
+    /// bounds earlier answers set, the last rejection is too recent, or the
+    /// website asked to wait, whatever the part.
+    pub fn refusal(&self, day: u8, part: Part, answer: &str, now: u64) -> Option<String> {
+        let number = answer.parse::<i64>().ok();
+        for s in self.of(day, part).filter(|s| s.verdict.is_judged()) {
+            let beyond = |bound: Verdict| {
+                let old = s.answer.parse::<i64>().ok();
+                match (bound, number, old) {
+                    (Verdict::TooHigh, Some(n), Some(old)) => n >= old,
+                    (Verdict::TooLow, Some(n), Some(old)) => n <= old,
+                    _ => false,
+                }
+            };
+            if s.verdict == Verdict::Right {
+                return Some(format!("already solved with {}", s.answer));
+            } else if s.answer == answer {
+                return Some(format!("{} was already submitted, {}", answer, s.verdict));
+            } else if beyond(s.verdict) {
+                return Some(format!("{} was {}", s.answer, s.verdict));
+            }
+        }
+
+        let waited = self.submissions.iter().filter_map(|s| match s.verdict {
+            Verdict::TooSoon(wait) => Some(s.time + wait.unwrap_or(RETRY_AFTER).as_secs()),
+            _ => None,
+        });
+        let wait = waited.max().unwrap_or(0).saturating_sub(now);
+        if wait > 0 {
+            return Some(format!("the website asked to wait, {}s left", wait));
+        }
+
+        let last = self
+            .of(day, part)
+            .filter(|s| s.verdict.is_judged())
+            .map(|s| s.time)
+            .max()?;
+        let wait = (last + RETRY_AFTER.as_secs()).saturating_sub(now);
+        if wait > 0 {
+            return Some(format!("the last answer was rejected, wait {}s", wait));
+        }
+        None
+    }
+}
+
+impl fmt::Display for History {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for s in self.submissions.iter() {
+            writeln!(
+                f,
+                "{} {} {} {} {}",
+                s.day,
+                s.part,
+                s.time,
+                s.verdict.name(),
+                s.answer
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{stub, System};
+
+    #[test]
+    fn test_parse_verdict() {
+        let page = |text: &str| format!("<main><article><p>{}</p></article></main>", text);
+        assert_eq!(
+            Some(Verdict::Right),
+            Verdict::parse(&page(
+                "That's the right answer! You are one gold star closer."
+            ))
+        );
+        assert_eq!(
+            Some(Verdict::TooHigh),
+            Verdict::parse(&page(
+                "That's not the right answer; your answer is too high."
+            ))
+        );
+        assert_eq!(
+            Some(Verdict::TooLow),
+            Verdict::parse(&page(
+                "That's not the right answer; your answer is too low."
+            ))
+        );
+        assert_eq!(
+            Some(Verdict::Wrong),
+            Verdict::parse(&page("That's not the right answer."))
+        );
+        assert_eq!(
+            Some(Verdict::TooSoon(Some(Duration::from_secs(65)))),
+            Verdict::parse(&page(
+                "You gave an answer too recently. You have 1m 5s left to wait."
+            ))
+        );
+        assert_eq!(
+            Some(Verdict::WrongLevel),
+            Verdict::parse(&page("You don't seem to be solving the right level."))
+        );
+        assert_eq!(None, Verdict::parse(&page("Something else")));
+    }
+
+    #[test]
+    fn test_history() {
+        let text = "1 1 1000 too_high 500\n1 1 1100 too_low 100\n1 1 1200 wrong 300\n";
+        let history = History::parse(text).unwrap();
+        assert_eq!(text, history.to_string());
+
+        let refusal = |answer: &str, now: u64| history.refusal(1, Part::One, answer, now);
+        assert_eq!(None, refusal("250", 1260));
+        assert!(refusal("250", 1259).unwrap().contains("wait 1s"));
+        assert!(refusal("300", 2000).unwrap().contains("already submitted"));
+        assert!(refusal("600", 2000)
+            .unwrap()
+            .contains("500 was wrong, too high"));
+        assert!(refusal("100", 2000).unwrap().contains("too low"));
+        assert_eq!(None, refusal("abc", 2000));
+        assert_eq!(None, history.refusal(1, Part::Two, "300", 1200));
+
+        let mut history = history;
+        history.record(Submission {
+            day: 1,
+            part: 1,
+            time: 1300,
+            verdict: Verdict::Right,
+            answer: "250".to_string(),
+        });
+        assert!(history
+            .refusal(1, Part::One, "251", 2000)
+            .unwrap()
+            .contains("already solved"));
+
+        assert!(History::parse("1 1 1000 maybe 5").is_err());
+    }
+
+    #[test]
+    fn test_history_too_soon() {
+        let text = "1 1 1000 wrong 300\n2 1 1100 too_soon:300 42\n2 2 1500 too_soon 7\n";
+        let history = History::parse(text).unwrap();
+        assert_eq!(text, history.to_string());
+
+        // The wait the website gave holds for every part
+        let refusal =
+            |day: u8, answer: &str, now: u64| history.refusal(day, Part::One, answer, now);
+        assert!(refusal(1, "250", 1399).unwrap().contains("1s left"));
+        assert!(refusal(1, "250", 1500).unwrap().contains("60s left"));
+        assert_eq!(None, refusal(1, "250", 1560));
+        // An answer submitted too soon was not judged, it can be submitted again
+        assert_eq!(None, refusal(2, "42", 1560));
+        assert!(refusal(1, "300", 1560)
+            .unwrap()
+            .contains("already submitted"));
+
+        assert!(History::parse("1 1 1000 too_soon:x 5").is_err());
+    }
+
+    #[test]
+    fn test_submit() {
+        let (base_url, requests) = stub::serve(vec![
+            (
+                200,
+                "<p>That's not the right answer; your answer is too low.</p>",
+            ),
+            (200, "<p>Oops</p>"),
+            (500, ""),
+        ]);
+        let verdict = submit(&System, &base_url, "abc", 2020, 4, Part::Two, "12 3").unwrap();
+        assert_eq!(Verdict::TooLow, verdict);
+        let (head, body) = requests.recv().unwrap();
+        assert!(head.starts_with("POST /2020/day/4/answer HTTP/1.1\r\n"));
+        assert!(head.contains("Cookie: session=abc\r\n"));
+        assert_eq!("level=2&answer=12%203", body);
+
+        assert!(submit(&System, &base_url, "abc", 2020, 4, Part::Two, "1").is_err());
+        assert!(submit(&System, &base_url, "abc", 2020, 4, Part::Two, "1").is_err());
+    }
+}
@@ -0,0 +1,191 @@
+use crate::Part;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How much the solvers tell about what they are doing, see [`debug!`] and
+/// [`trace!`].
+///
+/// [`debug!`]: crate::debug!
+/// [`trace!`]: crate::trace!
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Off,
+    /// Intermediate results, a few lines per part.
+    Debug,
+    /// Every step, possibly a lot of lines.
+    Trace,
+}
+
+impl Level {
+    /// The level of `-v` repeated `n` times.
+    pub fn from_verbosity(n: usize) -> Level {
+        match n {
+            0 => Level::Off,
+            1 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+
+    /// True if lines of `level` are written when running at this level.
+    pub fn allows(self, level: Level) -> bool {
+        level != Level::Off && level <= self
+    }
+
+    fn from_u8(n: u8) -> Level {
+        match n {
+            0 => Level::Off,
+            1 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Level::Off => "off",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+// Shared by every thread, so that it also applies to parts solved
+// concurrently. Off unless set, checking it is all a disabled line costs.
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Off as u8);
+
+thread_local! {
+    static SCOPE: Cell<Option<(u8, Option<Part>)>> = const { Cell::new(None) };
+    static CAPTURED: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn level() -> Level {
+    Level::from_u8(LEVEL.load(Ordering::Relaxed))
+}
+
+/// True if lines of `level` are written.
+#[inline]
+pub fn enabled(level: Level) -> bool {
+    self::level().allows(level)
+}
+
+/// Runs `f` with the lines it writes attributed to `day`, and to `part`
+/// unless it is parsing.
+pub fn scoped<T, F: FnOnce() -> T>(day: u8, part: Option<Part>, f: F) -> T {
+    struct Restore(Option<(u8, Option<Part>)>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SCOPE.with(|scope| scope.set(self.0));
+        }
+    }
+
+    let _restore = Restore(SCOPE.with(|scope| scope.replace(Some((day, part)))));
+    f()
+}
+
+/// Writes a line to stderr, prefixed with its level and the day and part it
+/// comes from. Use [`debug!`] and [`trace!`] instead, which only format the
+/// line when it is written.
+///
+/// [`debug!`]: crate::debug!
+/// [`trace!`]: crate::trace!
+pub fn write(level: Level, args: fmt::Arguments<'_>) {
+    let line = match SCOPE.with(Cell::get) {
+        Some((day, Some(part))) => format!("{} day {:02} part {}: {}", level, day, part, args),
+        Some((day, None)) => format!("{} day {:02} parse: {}", level, day, args),
+        None => format!("{}: {}", level, args),
+    };
+    let captured = CAPTURED.with(|captured| match captured.borrow_mut().as_mut() {
+        Some(lines) => {
+            lines.push(line.clone());
+            true
+        }
+        None => false,
+    });
+    if !captured {
+        eprintln!("{}", line);
+    }
+}
+
+/// Runs `f`, returning the lines it wrote on this thread instead of writing
+/// them to stderr.
+pub fn capture<T, F: FnOnce() -> T>(f: F) -> (T, Vec<String>) {
+    let previous = CAPTURED.with(|captured| captured.replace(Some(Vec::new())));
+    let res = f();
+    let lines = CAPTURED.with(|captured| captured.replace(previous));
+    (res, lines.unwrap_or_default())
+}
+
+/// Writes a line about an intermediate result when running with `-v`, see
+/// [`trace::write`](crate::trace::write).
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::trace::enabled($crate::trace::Level::Debug) {
+            $crate::trace::write($crate::trace::Level::Debug, format_args!($($arg)*));
+        }
+    };
+}
+
+/// Writes a line about a single step when running with `-vv`, see
+/// [`trace::write`](crate::trace::write).
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::trace::enabled($crate::trace::Level::Trace) {
+            $crate::trace::write($crate::trace::Level::Trace, format_args!($($arg)*));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels() {
+        assert_eq!(Level::Off, Level::from_verbosity(0));
+        assert_eq!(Level::Debug, Level::from_verbosity(1));
+        assert_eq!(Level::Trace, Level::from_verbosity(3));
+
+        assert!(Level::Debug.allows(Level::Debug));
+        assert!(!Level::Debug.allows(Level::Trace));
+        assert!(Level::Trace.allows(Level::Debug));
+        assert!(!Level::Off.allows(Level::Debug));
+        assert!(!Level::Trace.allows(Level::Off));
+    }
+
+    // The level is shared with the tests running alongside, so it is left
+    // alone and stays off
+    #[test]
+    fn test_write() {
+        let (answer, lines) = capture(|| {
+            write(Level::Debug, format_args!("outside of a day"));
+            scoped(3, None, || {
+                write(Level::Debug, format_args!("{} rows", 323))
+            });
+            scoped(3, Some(Part::Two), || {
+                write(Level::Trace, format_args!("slope {}:{}", 1, 2));
+                crate::debug!("not written while tracing is off");
+                42
+            })
+        });
+
+        assert_eq!(42, answer);
+        assert_eq!(
+            vec![
+                "debug: outside of a day",
+                "debug day 03 parse: 323 rows",
+                "trace day 03 part 2: slope 1:2",
+            ],
+            lines
+        );
+    }
+}
@@ -0,0 +1,771 @@
+use crate::answers::{self, Answers};
+use crate::bench::format_duration;
+use crate::days::Year;
+use crate::runner::{self, PartResult};
+use crate::{Part, Solver};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+#[cfg(unix)]
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+#[cfg(unix)]
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// A key press, as far as the dashboard cares.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Enter,
+    Back,
+    Char(char),
+}
+
+/// Decodes the bytes a terminal in raw mode sends for key presses. Escape
+/// sequences that are not understood are dropped, a lone escape is
+/// [`Key::Back`].
+pub fn parse_keys(bytes: &[u8]) -> Vec<Key> {
+    let mut keys = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let (key, len) = match &bytes[i..] {
+            [0x1b, b'[', b'A', ..] => (Some(Key::Up), 3),
+            [0x1b, b'[', b'B', ..] => (Some(Key::Down), 3),
+            [0x1b, b'[', b'5', b'~', ..] => (Some(Key::PageUp), 4),
+            [0x1b, b'[', b'6', b'~', ..] => (Some(Key::PageDown), 4),
+            [0x1b, b'[', rest @ ..] => {
+                // Skip to the final byte of an unknown sequence
+                let end = rest.iter().position(|b| (0x40..=0x7e).contains(b));
+                (None, 2 + end.map_or(rest.len(), |e| e + 1))
+            }
+            [0x1b, ..] => (Some(Key::Back), 1),
+            [b'\r', ..] | [b'\n', ..] => (Some(Key::Enter), 1),
+            [0x7f, ..] | [0x08, ..] => (Some(Key::Back), 1),
+            // Ctrl-C does not interrupt in raw mode
+            [0x03, ..] => (Some(Key::Char('q')), 1),
+            [b, ..] if b.is_ascii_graphic() || *b == b' ' => (Some(Key::Char(*b as char)), 1),
+            _ => (None, 1),
+        };
+        keys.extend(key);
+        i += len;
+    }
+    keys
+}
+
+/// The puzzle text in the top-level comments of a day's source, each
+/// comment a section. Comments within code are indented and left out.
+pub fn puzzle_text(source: &str) -> String {
+    let mut sections = Vec::new();
+    let mut rest = source;
+    while let Some(start) = rest.find("/*") {
+        let comment = &rest[start + 2..];
+        if start > 0 && !rest[..start].ends_with('\n') {
+            rest = comment;
+            continue;
+        }
+        let end = match comment.find("*/") {
+            Some(end) => end,
+            None => break,
+        };
+        // Also `/**`
+        sections.push(comment[..end].trim_start_matches('*').trim_matches('\n'));
+        rest = &comment[end + 2..];
+    }
+    sections.join("\n\n")
+}
+
+/// Where the dashboard keeps the last timings of `year` between sessions.
+pub fn timings_file(year: u16) -> PathBuf {
+    Path::new(crate::fetch::CACHE_DIR)
+        .join(year.to_string())
+        .join("timings.txt")
+}
+
+/// The last time each part took, keyed by day and part.
+///
+/// Stored as a text file with one `<day> <part> <nanoseconds>` entry per
+/// line.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Timings {
+    entries: BTreeMap<(u8, u8), Duration>,
+}
+
+impl Timings {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut entries = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let entry = match fields.as_slice() {
+                [day, part, nanos] => (|| {
+                    let day = day.parse::<u8>().ok()?;
+                    let part = part.parse::<u8>().ok().filter(|p| *p == 1 || *p == 2)?;
+                    Some(((day, part), Duration::from_nanos(nanos.parse().ok()?)))
+                })(),
+                _ => None,
+            };
+            match entry {
+                Some((key, elapsed)) => {
+                    entries.insert(key, elapsed);
+                }
+                None => {
+                    return Err(format!(
+                        "line {}: expected '<day> <part> <nanoseconds>'",
+                        i + 1
+                    ))
+                }
+            }
+        }
+        Ok(Self { entries })
+    }
+
+    /// Loads the timings at `path`, a missing file counts as empty.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => {
+                Self::parse(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        if let Some(dir) = path.as_ref().parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_string())
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<Duration> {
+        self.entries.get(&(day, part.number())).copied()
+    }
+
+    pub fn set(&mut self, day: u8, part: Part, elapsed: Duration) {
+        self.entries.insert((day, part.number()), elapsed);
+    }
+}
+
+impl std::fmt::Display for Timings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "# day part nanoseconds")?;
+        for ((day, part), elapsed) in self.entries.iter() {
+            writeln!(f, "{} {} {}", day, part, elapsed.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+/// Wraps `text` at word boundaries to lines of at most `width` characters,
+/// keeping the indentation of indented lines.
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        let indent: String = line.chars().take_while(|c| *c == ' ').collect();
+        let mut current = indent.clone();
+        for word in line.split_whitespace() {
+            let len = current.chars().count();
+            if len > indent.len() && len + 1 + word.chars().count() > width {
+                lines.push(current);
+                current = indent.clone();
+            }
+            if current.chars().count() > indent.len() {
+                current.push(' ');
+            }
+            current.push_str(word);
+        }
+        lines.push(current.trim_end().to_string());
+    }
+    lines
+}
+
+/// What is known about the answer to a part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Star {
+    /// No answer is recorded, so there is nothing to check against.
+    Unknown,
+    /// An answer is recorded, but the part was not run yet.
+    Unverified,
+    /// The last run gave the recorded answer.
+    Verified,
+    /// The last run failed or gave another answer.
+    Wrong,
+}
+
+impl Star {
+    fn symbol(self) -> char {
+        match self {
+            Star::Unknown => '·',
+            Star::Unverified => '☆',
+            Star::Verified => '★',
+            Star::Wrong => '✗',
+        }
+    }
+}
+
+struct Day {
+    solver: Box<dyn Solver>,
+    expected: [Option<String>; 2],
+    last: [Option<PartResult>; 2],
+}
+
+impl Day {
+    fn star(&self, part: Part) -> Star {
+        let i = part.number() as usize - 1;
+        match (&self.expected[i], &self.last[i]) {
+            (None, _) => Star::Unknown,
+            (Some(_), None) => Star::Unverified,
+            (Some(expected), Some(res)) if res.is_ok() && res.answer.as_ref() == Ok(expected) => {
+                Star::Verified
+            }
+            (Some(_), Some(_)) => Star::Wrong,
+        }
+    }
+}
+
+/// The state of the dashboard, driven by [`App::handle`] and drawn by
+/// [`App::render`], so that it can be tested without a terminal.
+pub struct App {
+    year: &'static Year,
+    days: Vec<Day>,
+    selected: usize,
+    /// The puzzle text of the open day, wrapped, and how far it is scrolled.
+    open: Option<(Vec<String>, usize)>,
+    /// Parts to run once the screen says so, see [`App::run_pending`].
+    pending: Vec<(usize, Part)>,
+    message: String,
+    /// Lines of puzzle text that fit on the screen, to page by.
+    page: usize,
+    width: usize,
+    /// The last timings, also of earlier sessions.
+    timings: Timings,
+}
+
+impl App {
+    pub fn new(year: &'static Year, answers: &Answers, timings: Timings) -> Self {
+        let days = year
+            .all()
+            .into_iter()
+            .map(|solver| {
+                let expected = Part::BOTH.map(|part| {
+                    answers
+                        .get(solver.day(), part, "input")
+                        .filter(|a| *a != answers::PENDING)
+                        .map(str::to_string)
+                });
+                Day {
+                    solver,
+                    expected,
+                    last: [None, None],
+                }
+            })
+            .collect();
+        App {
+            year,
+            days,
+            selected: 0,
+            open: None,
+            pending: Vec::new(),
+            message: String::new(),
+            page: 10,
+            width: 80,
+            timings,
+        }
+    }
+
+    pub fn timings(&self) -> &Timings {
+        &self.timings
+    }
+
+    pub fn star(&self, day: u8, part: Part) -> Option<Star> {
+        let day = self.days.iter().find(|d| d.solver.day() == day)?;
+        Some(day.star(part))
+    }
+
+    /// Whether there are parts waiting to be run.
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Reacts to a key, returns false to quit.
+    pub fn handle(&mut self, key: Key) -> bool {
+        if self.days.is_empty() {
+            return !matches!(key, Key::Char('q'));
+        }
+        self.message.clear();
+
+        match (self.open.is_some(), key) {
+            (_, Key::Char('q')) => return false,
+            (_, Key::Char(c @ ('1' | '2' | 'r'))) => {
+                let parts = match c {
+                    '1' => vec![Part::One],
+                    '2' => vec![Part::Two],
+                    _ => Part::BOTH.to_vec(),
+                };
+                let selected = self.selected;
+                self.queue(parts.into_iter().map(|p| (selected, p)));
+            }
+            (false, Key::Char('a')) => {
+                let all: Vec<(usize, Part)> = (0..self.days.len())
+                    .flat_map(|i| Part::BOTH.iter().map(move |p| (i, *p)))
+                    .collect();
+                self.queue(all);
+            }
+            (false, Key::Up) | (false, Key::Char('k')) => {
+                self.selected = self.selected.saturating_sub(1)
+            }
+            (false, Key::Down) | (false, Key::Char('j')) => {
+                self.selected = (self.selected + 1).min(self.days.len() - 1)
+            }
+            (false, Key::Enter) | (false, Key::Char('l')) => self.open_selected(),
+            (true, Key::Back) | (true, Key::Char('h')) => self.open = None,
+            (true, key) => {
+                let page = self.page.max(1);
+                if let Some((text, scroll)) = &mut self.open {
+                    let last = text.len().saturating_sub(page);
+                    *scroll = match key {
+                        Key::Up | Key::Char('k') => scroll.saturating_sub(1),
+                        Key::Down | Key::Char('j') => *scroll + 1,
+                        Key::PageUp | Key::Char('b') => scroll.saturating_sub(page),
+                        Key::PageDown | Key::Char(' ') => *scroll + page,
+                        _ => *scroll,
+                    }
+                    .min(last);
+                }
+            }
+            _ => {}
+        }
+        true
+    }
+
+    fn queue<I: IntoIterator<Item = (usize, Part)>>(&mut self, parts: I) {
+        self.pending.extend(parts);
+        self.message = format!("Running {} part(s)...", self.pending.len());
+    }
+
+    /// Shows the puzzle text of the selected day, which is read from the
+    /// checkout the binary was built in.
+    fn open_selected(&mut self) {
+        let day = self.days[self.selected].solver.day();
+        let path = Path::new(self.year.dir).join(format!("day{:02}.rs", day));
+        self.open = None;
+        match fs::read_to_string(&path) {
+            Ok(source) => self.open = Some((wrap(&puzzle_text(&source), self.width), 0)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                self.message = format!(
+                    "No puzzle text, {} is missing from the checkout aoc was built in",
+                    path.display()
+                )
+            }
+            Err(e) => self.message = format!("Could not read {}: {}", path.display(), e),
+        }
+    }
+
+    /// Runs the parts queued by the last keys.
+    pub fn run_pending(&mut self) {
+        let pending: Vec<(usize, Part)> = self.pending.drain(..).collect();
+        for (i, part) in pending.iter() {
+            let day = &mut self.days[*i];
+            let res = runner::run(day.solver.as_ref(), day.solver.input(), &[*part]).remove(0);
+            if res.answer.is_ok() {
+                self.timings.set(day.solver.day(), *part, res.elapsed);
+            }
+            day.last[part.number() as usize - 1] = Some(res);
+        }
+        let wrong = pending
+            .iter()
+            .filter(|(i, part)| self.days[*i].star(*part) == Star::Wrong)
+            .count();
+        self.message = match wrong {
+            0 => format!("Ran {} part(s)", pending.len()),
+            n => format!("Ran {} part(s), {} wrong", pending.len(), n),
+        };
+    }
+
+    /// The screen, as lines of at most `width` characters.
+    pub fn render(&mut self, width: usize, height: usize) -> Vec<String> {
+        if width != self.width {
+            self.width = width;
+            if self.open.is_some() {
+                self.open_selected();
+            }
+        }
+
+        let mut lines = match self.open.is_some() {
+            false => self.render_list(height),
+            true => self.render_day(height),
+        };
+        lines.truncate(height.saturating_sub(1));
+        while lines.len() + 1 < height {
+            lines.push(String::new());
+        }
+        lines.push(if self.message.is_empty() {
+            match self.open {
+                None => "↑/↓ select  enter open  1/2/r run part(s)  a run all  q quit",
+                Some(_) => "1/2/r run part(s)  ↑/↓ scroll  space/b page  esc back  q quit",
+            }
+            .to_string()
+        } else {
+            self.message.clone()
+        });
+        lines
+            .into_iter()
+            .map(|l| l.chars().take(width).collect())
+            .collect()
+    }
+
+    fn part_cell(&self, day: &Day, part: Part) -> String {
+        let time = match self.timings.get(day.solver.day(), part) {
+            Some(elapsed) => format_duration(elapsed),
+            None => String::new(),
+        };
+        format!("{} {:>9}", day.star(part).symbol(), time)
+    }
+
+    fn render_list(&self, height: usize) -> Vec<String> {
+        let stars = self
+            .days
+            .iter()
+            .flat_map(|d| Part::BOTH.iter().map(move |p| d.star(*p)))
+            .filter(|s| *s == Star::Verified)
+            .count();
+        let mut lines = vec![
+            format!(
+                "Advent of Code {}, {} of {} stars verified",
+                self.year.year,
+                stars,
+                self.days.len() * 2
+            ),
+            String::new(),
+            format!("   Day  {:<11}  {:<11}", "Part 1", "Part 2"),
+        ];
+        if self.days.is_empty() {
+            lines.push("   No days yet".to_string());
+        }
+
+        // Keep the selection in view
+        let room = height.saturating_sub(5).max(1);
+        let first = self.selected.saturating_sub(room - 1);
+        for (i, day) in self.days.iter().enumerate().skip(first).take(room) {
+            lines.push(format!(
+                "{} {:>4}  {}  {}",
+                if i == self.selected { '>' } else { ' ' },
+                day.solver.day(),
+                self.part_cell(day, Part::One),
+                self.part_cell(day, Part::Two)
+            ));
+        }
+        lines
+    }
+
+    fn render_day(&mut self, height: usize) -> Vec<String> {
+        let day = &self.days[self.selected];
+        let mut lines = vec![format!("Day {} of {}", day.solver.day(), self.year.year)];
+        for part in Part::BOTH.iter() {
+            let i = part.number() as usize - 1;
+            let expected = match &day.expected[i] {
+                Some(expected) => format!("expected {}", expected),
+                None => "no answer recorded".to_string(),
+            };
+            let outcome = match &day.last[i] {
+                None => match self.timings.get(day.solver.day(), *part) {
+                    Some(elapsed) => format!(
+                        "not run yet, {}, last took {}",
+                        expected,
+                        format_duration(elapsed)
+                    ),
+                    None => format!("not run yet, {}", expected),
+                },
+                Some(res) => {
+                    let answer = match &res.answer {
+                        Ok(answer) => answer.clone(),
+                        Err(e) => format!("{}: {}", res.status, e),
+                    };
+                    let mut outcome = format!("{} in {}", answer, format_duration(res.elapsed));
+                    if day.star(*part) == Star::Wrong {
+                        outcome.push_str(&format!(", {}", expected));
+                    }
+                    outcome
+                }
+            };
+            lines.push(format!(
+                "Part {}: {} {}",
+                part,
+                day.star(*part).symbol(),
+                outcome
+            ));
+        }
+        lines.push("─".repeat(self.width));
+
+        // Room left for the text, below it a line with the position
+        self.page = height.saturating_sub(lines.len() + 2).max(1);
+        let (text, scroll) = self.open.as_ref().expect("a day is open");
+        lines.extend(text.iter().skip(*scroll).take(self.page).cloned());
+        while lines.len() < 4 + self.page {
+            lines.push(String::new());
+        }
+        lines.push(format!(
+            "─ lines {}-{} of {} ",
+            (*scroll + 1).min(text.len()),
+            (*scroll + self.page).min(text.len()),
+            text.len()
+        ));
+        lines
+    }
+}
+
+// The terminal is driven with `stty` and escape sequences, which only Unix
+// terminals are sure to understand
+
+/// Runs `stty` on the terminal, returning what it printed.
+#[cfg(unix)]
+fn stty(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other("stdin is not a terminal"));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Keeps the terminal in raw mode on the alternate screen, restoring it
+/// when dropped, also when panicking.
+#[cfg(unix)]
+struct Screen {
+    saved: String,
+}
+
+#[cfg(unix)]
+impl Screen {
+    fn enter() -> io::Result<Self> {
+        let saved = stty(&["-g"])?;
+        stty(&["raw", "-echo"])?;
+        print!("\x1b[?1049h\x1b[?25l");
+        io::stdout().flush()?;
+        Ok(Screen { saved })
+    }
+
+    /// Rows and columns, with a fallback if the terminal does not say.
+    fn size(&self) -> (usize, usize) {
+        stty(&["size"])
+            .ok()
+            .and_then(|size| {
+                let (rows, cols) = size.split_once(' ')?;
+                Some((rows.parse().ok()?, cols.parse().ok()?))
+            })
+            .filter(|(rows, cols)| *rows > 0 && *cols > 0)
+            .unwrap_or((24, 80))
+    }
+
+    fn draw(&self, app: &mut App) -> io::Result<()> {
+        let (rows, cols) = self.size();
+        let lines = app.render(cols, rows);
+        // Raw mode leaves line endings to us
+        let mut out = String::from("\x1b[H");
+        for (i, line) in lines.iter().enumerate() {
+            out.push_str(line);
+            out.push_str("\x1b[K");
+            if i + 1 < lines.len() {
+                out.push_str("\r\n");
+            }
+        }
+        out.push_str("\x1b[J");
+        let mut stdout = io::stdout();
+        stdout.write_all(out.as_bytes())?;
+        stdout.flush()
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Screen {
+    fn drop(&mut self) {
+        print!("\x1b[?25h\x1b[?1049l");
+        let _ = io::stdout().flush();
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+/// Shows the dashboard of `year` on the terminal until `q` is pressed, keeping
+/// the timings of the parts it runs in `timings_path`.
+#[cfg(unix)]
+pub fn run(year: &'static Year, answers: &Answers, timings_path: &Path) -> io::Result<()> {
+    let mut app = App::new(year, answers, Timings::load(timings_path)?);
+    let screen = Screen::enter()?;
+    let mut stdin = io::stdin();
+    let mut buf = [0; 64];
+    loop {
+        screen.draw(&mut app)?;
+        if app.has_pending() {
+            app.run_pending();
+            app.timings().save(timings_path)?;
+            continue;
+        }
+
+        let n = stdin.read(&mut buf)?;
+        if n == 0 {
+            return Ok(());
+        }
+        for key in parse_keys(&buf[..n]) {
+            if !app.handle(key) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// The dashboard needs a Unix terminal.
+#[cfg(not(unix))]
+pub fn run(_year: &'static Year, _answers: &Answers, _timings_path: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "the dashboard is only supported on Unix terminals",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn test_parse_keys() {
+        assert_eq!(
+            vec![
+                Key::Up,
+                Key::Char('j'),
+                Key::PageDown,
+                Key::Enter,
+                Key::Back,
+                Key::Char(' '),
+                Key::Back
+            ],
+            parse_keys(b"\x1b[Aj\x1b[6~\r\x7f \x1b")
+        );
+        // Unknown sequences, like F5, are dropped
+        assert_eq!(vec![Key::Char('q')], parse_keys(b"\x1b[15~q"));
+        assert_eq!(vec![Key::Char('q')], parse_keys(b"\x03"));
+    }
+
+    #[test]
+    fn test_puzzle_text() {
+        let source = "/**\n--- Day 1 ---\n\nText\n*/\n\nfn f() {\n    /* not this */\n}\n\n/*--- Part Two ---\n*/\nuse x; /* nor this */";
+        assert_eq!(
+            "--- Day 1 ---\n\nText\n\n--- Part Two ---",
+            puzzle_text(source)
+        );
+        assert_eq!("", puzzle_text("use x;"));
+
+        // The second part comes after the code of the first one
+        let text = puzzle_text(include_str!("days/y2020/day02.rs"));
+        assert!(
+            text.starts_with("--- Day 2: Password Philosophy ---"),
+            "{}",
+            text
+        );
+        assert!(text.contains("--- Part Two ---"), "{}", text);
+    }
+
+    #[test]
+    fn test_timings() {
+        let text = "# day part nanoseconds\n1 1 1500\n3 2 42000000\n";
+        let timings = Timings::parse(text).unwrap();
+        assert_eq!(text, timings.to_string());
+        assert_eq!(Some(Duration::from_nanos(1500)), timings.get(1, Part::One));
+        assert_eq!(None, timings.get(1, Part::Two));
+        assert!(Timings::parse("1 3 5").is_err());
+        assert!(Timings::parse("1 1 fast").is_err());
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(
+            vec!["one two", "three", "", "    four", "    five"],
+            wrap("one two three\n\n    four five", 9)
+        );
+        assert_eq!(vec!["overlong", "x"], wrap("overlong x", 4));
+    }
+
+    #[test]
+    fn test_app() {
+        let year = days::year(2020).unwrap();
+        let mut answers = Answers::default();
+        answers.set(1, Part::One, "input", "1016964");
+        answers.set(1, Part::Two, "input", "1");
+        answers.set(2, Part::One, "input", answers::PENDING);
+        let mut timings = Timings::default();
+        timings.set(2, Part::One, Duration::from_millis(3));
+        let mut app = App::new(year, &answers, timings);
+        assert_eq!(Some(Star::Unverified), app.star(1, Part::One));
+        assert_eq!(Some(Star::Unknown), app.star(2, Part::One));
+
+        assert!(app.handle(Key::Char('r')));
+        assert!(app.has_pending());
+        app.run_pending();
+        assert_eq!(Some(Star::Verified), app.star(1, Part::One));
+        assert_eq!(Some(Star::Wrong), app.star(1, Part::Two));
+        assert!(app.timings().get(1, Part::Two).is_some());
+
+        let screen = app.render(60, 20);
+        assert_eq!(20, screen.len());
+        let stars = year.all().len() * 2;
+        let title = format!("Advent of Code 2020, 1 of {} stars verified", stars);
+        assert_eq!(title, screen[0]);
+        assert!(screen[3].starts_with(">    1  ★ "), "{:?}", screen[3]);
+        assert!(screen[4].starts_with("     2  · "), "{:?}", screen[4]);
+        // Timings of earlier sessions are shown until the part runs again
+        let earlier = format_duration(Duration::from_millis(3));
+        assert!(screen[4].contains(&earlier), "{:?}", screen[4]);
+        assert_eq!("Ran 2 part(s), 1 wrong", screen[19]);
+
+        app.handle(Key::Down);
+        app.handle(Key::Down);
+        app.handle(Key::Enter);
+        let screen = app.render(60, 20);
+        assert_eq!("Day 3 of 2020", screen[0]);
+        assert!(screen[1].starts_with("Part 1: · not run yet"));
+        assert_eq!("--- Day 3: Toboggan Trajectory ---", screen[4]);
+        assert!(
+            screen[18].starts_with("─ lines 1-14 of "),
+            "{:?}",
+            screen[18]
+        );
+
+        app.handle(Key::PageDown);
+        let screen = app.render(60, 20);
+        assert!(
+            screen[18].starts_with("─ lines 15-28 of "),
+            "{:?}",
+            screen[18]
+        );
+
+        app.handle(Key::Back);
+        assert!(app.render(60, 20)[5].starts_with(">    3"));
+        assert!(!app.handle(Key::Char('q')));
+    }
+
+    #[test]
+    fn test_app_without_sources() {
+        static MOVED: Year = Year::new(2020, "/moved/src/days/y2020", days::y2020::all);
+        let mut app = App::new(&MOVED, &Answers::default(), Timings::default());
+        app.handle(Key::Enter);
+        let screen = app.render(200, 20);
+        assert!(screen[0].starts_with("Advent of Code 2020"));
+        assert!(
+            screen[19].starts_with("No puzzle text, /moved/src/days/y2020/day01.rs is missing"),
+            "{:?}",
+            screen[19]
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use crate::answers::{Answers, Key};
+use crate::Part;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// The files of `day` in `dir`: its source, input and example inputs with
+/// their answers, sorted by name.
+pub fn day_files<P: AsRef<Path>>(dir: P, day: u8) -> io::Result<Vec<PathBuf>> {
+    let source = format!("day{:02}.rs", day);
+    let prefix = format!("day{:02}-", day);
+
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let matches = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name == source || name.starts_with(&prefix));
+        if matches && path.is_file() {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Polls the files of a day for changes by their modification time and
+/// size, which needs no platform specific notification API.
+///
+/// Files that appear or disappear count as changed too, so that a new
+/// example input is picked up.
+#[derive(Debug)]
+pub struct Watcher {
+    dir: PathBuf,
+    day: u8,
+    stamps: BTreeMap<PathBuf, (SystemTime, u64)>,
+}
+
+impl Watcher {
+    pub fn new<P: AsRef<Path>>(dir: P, day: u8) -> io::Result<Self> {
+        let mut watcher = Watcher {
+            dir: dir.as_ref().to_path_buf(),
+            day,
+            stamps: BTreeMap::new(),
+        };
+        watcher.stamps = watcher.snapshot()?;
+        Ok(watcher)
+    }
+
+    fn snapshot(&self) -> io::Result<BTreeMap<PathBuf, (SystemTime, u64)>> {
+        let mut stamps = BTreeMap::new();
+        for path in day_files(&self.dir, self.day)? {
+            // A file removed since it was listed is simply left out
+            if let Ok(meta) = fs::metadata(&path) {
+                stamps.insert(path, (meta.modified()?, meta.len()));
+            }
+        }
+        Ok(stamps)
+    }
+
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+        self.stamps.keys().map(PathBuf::as_path)
+    }
+
+    /// The files changed, added or removed since the last call.
+    pub fn changed(&mut self) -> io::Result<Vec<PathBuf>> {
+        let stamps = self.snapshot()?;
+        let mut changed: Vec<PathBuf> = stamps
+            .iter()
+            .filter(|(path, stamp)| self.stamps.get(*path) != Some(stamp))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.extend(
+            self.stamps
+                .keys()
+                .filter(|path| !stamps.contains_key(*path))
+                .cloned(),
+        );
+        changed.sort();
+        self.stamps = stamps;
+        Ok(changed)
+    }
+}
+
+/// Describes every answer in `current`, noting how it differs from
+/// `previous`, and every answer that is gone.
+pub fn describe_changes(previous: &Answers, current: &Answers) -> Vec<String> {
+    let get = |answers: &'_ Answers, (day, part, input): &Key| {
+        Part::from_number(*part)
+            .and_then(|part| answers.get(*day, part, input))
+            .map(str::to_string)
+    };
+
+    let mut lines = Vec::new();
+    for (key, answer) in current.iter() {
+        let (day, part, input) = key;
+        let note = match get(previous, key) {
+            Some(old) if old == *answer => " (unchanged)".to_string(),
+            Some(old) => format!(" (was {})", old),
+            None => String::new(),
+        };
+        lines.push(format!(
+            "Day {} part {} ({}): {}{}",
+            day, part, input, answer, note
+        ));
+    }
+    for (key, answer) in previous.iter() {
+        let (day, part, input) = key;
+        if get(current, key).is_none() {
+            lines.push(format!(
+                "Day {} part {} ({}): no answer (was {})",
+                day, part, input, answer
+            ));
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watcher() {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["day03.rs", "day03-input.txt", "day13.rs", "day30-input.txt"].iter() {
+            fs::write(dir.join(name), "x").unwrap();
+        }
+
+        let mut watcher = Watcher::new(&dir, 3).unwrap();
+        let names: Vec<&str> = watcher
+            .files()
+            .map(|p| p.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(vec!["day03-input.txt", "day03.rs"], names);
+        assert!(watcher.changed().unwrap().is_empty());
+
+        fs::write(dir.join("day03-input.txt"), "xy").unwrap();
+        fs::write(dir.join("day03-test-input.txt"), "").unwrap();
+        fs::write(dir.join("day13.rs"), "xy").unwrap();
+        assert_eq!(
+            vec![
+                dir.join("day03-input.txt"),
+                dir.join("day03-test-input.txt")
+            ],
+            watcher.changed().unwrap()
+        );
+
+        fs::remove_file(dir.join("day03.rs")).unwrap();
+        assert_eq!(vec![dir.join("day03.rs")], watcher.changed().unwrap());
+        assert!(watcher.changed().unwrap().is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_describe_changes() {
+        let mut previous = Answers::default();
+        previous.set(3, Part::One, "input", "7");
+        previous.set(3, Part::Two, "input", "336");
+        let mut current = Answers::default();
+        current.set(3, Part::One, "input", "7");
+        current.set(3, Part::Two, "input", "340");
+
+        assert_eq!(
+            vec![
+                "Day 3 part 1 (input): 7 (unchanged)",
+                "Day 3 part 2 (input): 340 (was 336)"
+            ],
+            describe_changes(&previous, &current)
+        );
+        assert_eq!(
+            vec![
+                "Day 3 part 1 (input): no answer (was 7)",
+                "Day 3 part 2 (input): no answer (was 336)"
+            ],
+            describe_changes(&previous, &Answers::default())
+        );
+        assert_eq!(
+            vec!["Day 3 part 1 (input): 7", "Day 3 part 2 (input): 340"],
+            describe_changes(&Answers::default(), &current)
+        );
+    }
+}